@@ -5,7 +5,8 @@ use blib "xs/blib";
 use blib "rs/blib";
 
 use XSBench::XS qw/xs_sum xs_gcd/;
-use XSBench::RS qw/rs_sum_loop rs_sum_iter rs_gcd/;
+use XSBench::RS qw/rs_sum_loop rs_sum_iter rs_gcd rs_first/;
+use List::Util ();
 use Dumbbench;
 
 sub bench {
@@ -74,5 +75,28 @@ sub bench_gcd {
     print "pp=$pp, xs=$xs, rs=$rs\n";
 }
 
+sub pp_first {
+    my $code = shift;
+    foreach (@_) {
+        return $_ if $code->();
+    }
+    return undef;
+}
+
+sub bench_first {
+    my $specimen = [ 1 .. 100_000 ];
+    my $needle = $specimen->[-1];
+
+    my ($pp, $xs, $rs);
+
+    bench(
+        pp => sub { $pp = pp_first(sub { $_ == $needle }, @$specimen) },
+        xs => sub { $xs = List::Util::first(sub { $_ == $needle }, @$specimen) },
+        rs => sub { $rs = rs_first(sub { $_ == $needle }, @$specimen) },
+    );
+    print "pp=$pp, xs=$xs, rs=$rs\n";
+}
+
 bench_sum();
 bench_gcd();
+bench_first();
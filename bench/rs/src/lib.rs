@@ -3,7 +3,7 @@ extern crate perl_xs;
 #[macro_use]
 extern crate perl_sys;
 
-use perl_xs::{ AV, IV, NV };
+use perl_xs::{ AV, IV, NV, SV };
 
 xs! {
     package XSBench::RS;
@@ -39,6 +39,12 @@ xs! {
 
         res
     }
+
+    sub rs_first(ctx, block: SV) {
+        let items: Vec<SV> = (1..ctx.st_items()).map(|i| ctx.st_fetch(i).unwrap()).collect();
+        let mut callback = ctx.new_callback(&block);
+        items.into_iter().find(|item| callback.call_topic(item).is_true())
+    }
 }
 
 xs! {
@@ -20,6 +20,17 @@ pub fn from_kv(input: TokenStream) -> TokenStream {
     gen.parse().unwrap()
 }
 
+#[proc_macro_derive(PerlClass, attributes(perlxs))]
+pub fn perl_class(input: TokenStream) -> TokenStream {
+    let ast = syn::parse_macro_input(&input.to_string()).unwrap();
+
+    // Build the impl
+    let gen = impl_perl_class(&ast);
+
+    // Return the generated impl
+    gen.parse().unwrap()
+}
+
 fn impl_from_kv(ast: &syn::MacroInput) -> quote::Tokens {
     let ident = &ast.ident;
     let ident_lit = Lit::Str(ast.ident.to_string(), StrStyle::Cooked);
@@ -158,3 +169,154 @@ fn impl_from_kv(ast: &syn::MacroInput) -> quote::Tokens {
         };
     }
 }
+
+fn impl_perl_class(ast: &syn::MacroInput) -> quote::Tokens {
+    let ident = &ast.ident;
+
+    let errors = internals::error::Errors::new();
+
+    let container = internals::container::Container::from_ast(&errors, ast);
+
+    let fields = match ast.body {
+        syn::Body::Struct(ref vdata) => match vdata {
+            &VariantData::Struct(ref fields) => internals::ast::fields_from_ast(&errors, fields),
+            &VariantData::Tuple(_) | &VariantData::Unit => {
+                panic!("You can only derive this for normal structs!");
+            }
+        },
+        syn::Body::Enum(_) => panic!("You can only derive this on structs!"),
+    };
+
+    if container.package.is_none() {
+        errors.error("missing perlxs container attribute `package`");
+    }
+
+    errors.check().unwrap();
+
+    let package = container.package.clone().unwrap();
+    let package_path = Ident::new(package.clone());
+    let package_lit = Lit::Str(package.clone(), StrStyle::Cooked);
+
+    let mut subs = Vec::new();
+    let mut names = Vec::new();
+
+    if container.clone {
+        subs.push(quote! {
+            xs! {
+                @sub #package_path,
+                fn new(ctx, class: _perlxs::SV) {
+                    _perlxs::class::construct_cloneable::<#ident>(ctx, class)
+                }
+            }
+        });
+    } else {
+        subs.push(quote! {
+            xs! {
+                @sub #package_path,
+                fn new(ctx, class: _perlxs::SV) {
+                    _perlxs::class::construct::<#ident>(ctx, class)
+                }
+            }
+        });
+
+        subs.push(quote! {
+            xs! {
+                @sub #package_path,
+                fn CLONE_SKIP(_ctx) {
+                    1 as _perlxs::IV
+                }
+            }
+        });
+        names.push(Ident::new("CLONE_SKIP"));
+    }
+    names.push(Ident::new("new"));
+
+    subs.push(quote! {
+        xs! {
+            @sub #package_path,
+            fn DESTROY(ctx, this: _perlxs::SV) {
+                _perlxs::class::destroy::<#ident>(ctx, this)
+            }
+        }
+    });
+    names.push(Ident::new("DESTROY"));
+
+    for field in fields.iter().filter(|f| f.get || f.set) {
+        let name = &field.ident;
+        let ty = &field.ty;
+        let full_name_lit = Lit::Str(format!("{}::{}", package, name), StrStyle::Cooked);
+
+        let sub = if !field.set {
+            quote! {
+                xs! {
+                    @sub #package_path,
+                    fn #name(ctx, this: _perlxs::class::Object<#ident>) {
+                        _perlxs::class::read_only(ctx, #full_name_lit);
+                        this.#name.clone()
+                    }
+                }
+            }
+        } else {
+            let arg = if field.optional {
+                quote! { _perlxs::class::accessor_arg_opt::<#ty>(ctx, #full_name_lit) }
+            } else {
+                quote! { _perlxs::class::accessor_arg::<#ty>(ctx, #full_name_lit) }
+            };
+
+            // Borrow the object mutably only when a new value is given, so that reading a field
+            // does not conflict with other live borrows. The value is converted before the
+            // borrow, since its get-magic or overloading may call back into the object.
+            quote! {
+                xs! {
+                    @sub #package_path,
+                    fn #name(ctx) {
+                        if ctx.st_items() > 1 {
+                            let value = #arg;
+                            let mut this = _perlxs::class::invocant::<_perlxs::class::ObjectMut<#ident>>(ctx, #full_name_lit);
+                            this.#name = value;
+                            this.#name.clone()
+                        } else {
+                            let this = _perlxs::class::invocant::<_perlxs::class::Object<#ident>>(ctx, #full_name_lit);
+                            this.#name.clone()
+                        }
+                    }
+                }
+            }
+        };
+
+        subs.push(sub);
+        names.push(name.clone());
+    }
+
+    let entries: Vec<_> = names
+        .iter()
+        .map(|name| {
+            let name_lit = Lit::Str(format!("{}::{}", package, name), StrStyle::Cooked);
+            quote! {
+                (#name_lit, #name as _perlxs::raw::XSUBADDR_t)
+            }
+        })
+        .collect();
+
+    let dummy_const = Ident::new(format!("_IMPL_PERLXS_PERLCLASS_FOR_{}", ident));
+
+    quote! {
+        #[allow(non_upper_case_globals, non_snake_case)]
+        const #dummy_const: () = {
+            extern crate perl_xs as _perlxs;
+
+            impl _perlxs::class::Class for #ident {
+                const PACKAGE: &'static str = #package_lit;
+            }
+
+            #(#subs)*
+
+            impl #ident {
+                /// Subroutines generated for the class, to be registered by the bootstrap function.
+                pub const PERL_XS: &'static [(&'static str, _perlxs::raw::XSUBADDR_t)] = &[
+                    #(#entries,)*
+                ];
+            }
+        };
+    }
+}
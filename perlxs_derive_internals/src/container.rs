@@ -0,0 +1,52 @@
+use syn;
+use syn::MetaItem::{NameValue, Word};
+use syn::NestedMetaItem::{Literal, MetaItem};
+
+use crate::error::Errors;
+use crate::field::{get_meta_items, get_string_from_lit};
+
+#[derive(Debug)]
+pub struct Container {
+    pub package: Option<String>,
+    pub clone: bool,
+}
+
+impl Container {
+    /// Extract the `#[perlxs(...)]` attributes from a struct.
+    pub fn from_ast(errors: &Errors, input: &syn::MacroInput) -> Self {
+        let mut package = None;
+        let mut clone = false;
+
+        for meta_items in input.attrs.iter().filter_map(get_meta_items) {
+            for meta_item in meta_items {
+                match meta_item {
+                    // Parse `#[perlxs(package = "Foo::Bar")]`
+                    MetaItem(NameValue(ref name, ref lit)) if name == "package" => {
+                        if let Ok(s) = get_string_from_lit(errors, name.as_ref(), name.as_ref(), lit) {
+                            package = Some(s);
+                        }
+                    }
+                    // Parse `#[perlxs(clone)]`
+                    MetaItem(Word(ref name)) if name == "clone" => {
+                        clone = true;
+                    }
+                    MetaItem(ref meta_item) => {
+                        errors.error(format!(
+                            "unknown perlxs container attribute `{}`",
+                            meta_item.name()
+                        ));
+                    }
+
+                    Literal(_) => {
+                        errors.error("unexpected literal in perlxs container attribute");
+                    }
+                }
+            }
+        }
+
+        Container {
+            package: package,
+            clone: clone,
+        }
+    }
+}
@@ -1,6 +1,6 @@
 use syn;
 use syn::{PathParameters, Ty};
-use syn::MetaItem::{List, NameValue};
+use syn::MetaItem::{List, NameValue, Word};
 use syn::NestedMetaItem::{Literal, MetaItem};
 
 use crate::error::Errors;
@@ -12,12 +12,16 @@ pub struct Field {
     pub keys: Vec<String>,
     pub ty: syn::Ty,
     pub optional: bool,
+    pub get: bool,
+    pub set: bool,
 }
 
 impl Field {
     /// Extract the `#[perlxs(...)]` attributes from a struct field.
     pub fn from_ast(errors: &Errors, index: usize, field: &syn::Field) -> Self {
         let mut keys = Vec::new();
+        let mut get = false;
+        let mut set = false;
 
         let name = match field.ident {
             Some(ref ident) => ident.to_string(),
@@ -33,9 +37,17 @@ impl Field {
                             keys.push(s);
                         }
                     }
+                    // Parse `#[perlxs(get)]`
+                    MetaItem(Word(ref name)) if name == "get" => {
+                        get = true;
+                    }
+                    // Parse `#[perlxs(set)]`
+                    MetaItem(Word(ref name)) if name == "set" => {
+                        set = true;
+                    }
                     MetaItem(ref meta_item) => {
                         errors.error(format!(
-                            "unknown perlxs container attribute `{}`",
+                            "unknown perlxs field attribute `{}`",
                             meta_item.name()
                         ));
                     }
@@ -63,6 +75,8 @@ impl Field {
             keys: keys,
             ty: inner_ty,
             optional: optional,
+            get: get,
+            set: set,
         }
     }
 }
@@ -89,7 +103,7 @@ pub fn get_meta_items(attr: &syn::Attribute) -> Option<Vec<syn::NestedMetaItem>>
     }
 }
 
-fn get_string_from_lit(errors: &Errors, attr_name: &str, meta_item_name: &str, lit: &syn::Lit) -> Result<String, ()> {
+pub fn get_string_from_lit(errors: &Errors, attr_name: &str, meta_item_name: &str, lit: &syn::Lit) -> Result<String, ()> {
     if let syn::Lit::Str(ref s, _) = *lit {
         Ok(s.clone())
     } else {
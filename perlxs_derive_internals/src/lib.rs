@@ -3,3 +3,4 @@ extern crate syn;
 pub mod error;
 pub mod field;
 pub mod ast;
+pub mod container;
@@ -1,7 +1,7 @@
 use std::marker::PhantomData;
 
 use crate::SV;
-use crate::convert::{FromSV, TryFromSV};
+use crate::convert::{get_magic, FromSV, IntoSV, TryFromSV};
 use crate::handle::Owned;
 use crate::raw;
 use crate::raw::SSize_t;
@@ -141,6 +141,11 @@ impl AV {
 impl TryFromSV for AV {
     type Error = &'static str;
     unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<AV, Self::Error> {
+        get_magic(pthx, raw);
+        Self::try_from_sv_nomg(pthx, raw)
+    }
+
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<AV, Self::Error> {
         if pthx.ouroboros_sv_rok(raw) == 0 {
             return Err("not an array reference");
         }
@@ -152,6 +157,19 @@ impl TryFromSV for AV {
     }
 }
 
+/// Create a new reference to the array.
+impl IntoSV for AV {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        assert!(self.pthx() == pthx);
+        unsafe {
+            let raw = self.as_ptr();
+            self.0.incref();
+            SV::from_raw_owned(pthx, pthx.newRV_noinc(raw as *mut _))
+        }
+    }
+}
+
 pub struct IterAV<'a, T> {
     av: &'a AV,
     pos: SSize_t,
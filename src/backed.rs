@@ -0,0 +1,57 @@
+//! SVs with string buffers owned by Rust.
+
+use std::os::raw::c_char;
+use std::sync::Arc;
+
+use crate::raw;
+use crate::SV;
+
+/// Owner of the buffer, attached to the SV.
+///
+/// The type is private, so that the owner can't be detached while the SV points to its buffer.
+struct Backing<B>(Arc<B>);
+
+impl<B> Clone for Backing<B> {
+    fn clone(&self) -> Self {
+        Backing(self.0.clone())
+    }
+}
+
+/// Create a read-only SV whose string buffer is borrowed from `owner`.
+///
+/// The owner is attached to the SV and dropped when the SV is freed. `SvLEN` is set to zero, so
+/// that Perl does not try to free or grow the buffer.
+///
+/// Perl expects string buffers to be terminated with a NUL byte, so the buffer must end with one.
+/// The terminator is not part of the string.
+pub(crate) fn new_sv<B, F>(pthx: raw::Interpreter, owner: B, bytes: F, utf8: bool) -> SV
+where
+    B: Send + Sync + 'static,
+    F: FnOnce(&B) -> &[u8],
+{
+    let owner = Arc::new(owner);
+    let (ptr, len) = {
+        let buf = bytes(&owner);
+        match buf.split_last() {
+            Some((&0, string)) => (buf.as_ptr(), string.len()),
+            _ => panic!("buffer of a backed SV must end with a NUL byte"),
+        }
+    };
+
+    unsafe {
+        let sv = SV::from_raw_owned(pthx, pthx.newSV(0));
+        let raw = sv.as_ptr();
+        pthx.sv_upgrade(raw, raw::SVt_PV as _);
+        sv.attach_cloneable(Backing(owner));
+
+        pthx.ouroboros_sv_pv_set(raw, ptr as *mut c_char);
+        pthx.ouroboros_sv_cur_set(raw, len as raw::STRLEN);
+        pthx.ouroboros_sv_len_set(raw, 0);
+
+        (*raw).sv_flags |= raw::SVf_POK | raw::SVp_POK | raw::SVf_READONLY;
+        if utf8 {
+            (*raw).sv_flags |= raw::SVf_UTF8;
+        }
+        sv
+    }
+}
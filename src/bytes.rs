@@ -0,0 +1,193 @@
+use std::ffi::{OsStr, OsString};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::{slice, str};
+
+#[cfg(unix)]
+use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+use crate::convert::{get_magic, IntoSV, TryFromSV};
+use crate::error::BytesError;
+use crate::raw;
+use crate::SV;
+
+/// Byte string.
+///
+/// Unlike `String`, which holds characters, this type holds raw bytes: it never sets the UTF8
+/// flag when passed to Perl, and conversion from Perl fails if the string contains characters
+/// above 255, the same way [`utf8::downgrade`](http://perldoc.perl.org/utf8.html) does.
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use perl_xs::Bytes;
+/// # xs! {
+/// #   package Dummy;
+/// sub reverse_bytes(_ctx, data: Bytes) {
+///     let mut data = data.into_inner();
+///     data.reverse();
+///     Bytes::from(data)
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Bytes(pub Vec<u8>);
+
+impl Bytes {
+    /// Return the underlying vector.
+    #[inline]
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0
+    }
+}
+
+impl From<Vec<u8>> for Bytes {
+    #[inline]
+    fn from(v: Vec<u8>) -> Bytes {
+        Bytes(v)
+    }
+}
+
+impl Deref for Bytes {
+    type Target = Vec<u8>;
+
+    #[inline]
+    fn deref(&self) -> &Vec<u8> {
+        &self.0
+    }
+}
+
+impl DerefMut for Bytes {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Vec<u8> {
+        &mut self.0
+    }
+}
+
+impl TryFromSV for Bytes {
+    type Error = BytesError;
+
+    #[inline]
+    unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        Vec::<u8>::try_from_sv(pthx, raw).map(Bytes)
+    }
+
+    #[inline]
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        Vec::<u8>::try_from_sv_nomg(pthx, raw).map(Bytes)
+    }
+}
+
+impl IntoSV for Bytes {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        (&self.0[..]).into_sv(pthx)
+    }
+}
+
+/// Bytes of a byte string. Character strings are downgraded, characters above 255 and malformed
+/// UTF-8 are an error.
+impl TryFromSV for Vec<u8> {
+    type Error = BytesError;
+
+    #[inline]
+    unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, raw);
+        Self::try_from_sv_nomg(pthx, raw)
+    }
+
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        let mut len = 0;
+        let ptr = pthx.sv_2pv_flags(raw, &mut len, 0);
+        let bytes = slice::from_raw_parts(ptr as *const u8, len as usize);
+        if pthx.ouroboros_sv_utf8(raw) == 0 {
+            return Ok(bytes.to_vec());
+        }
+        downgrade(str::from_utf8(bytes).map_err(BytesError::Malformed)?)
+    }
+}
+
+fn downgrade(s: &str) -> Result<Vec<u8>, BytesError> {
+    s.chars()
+        .enumerate()
+        .map(|(offset, c)| {
+            if (c as u32) < 256 {
+                Ok(c as u8)
+            } else {
+                Err(BytesError::WideChar(offset))
+            }
+        })
+        .collect()
+}
+
+/// Create a byte string, without the UTF8 flag.
+impl<'a> IntoSV for &'a [u8] {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        unsafe {
+            let svp = pthx.newSVpvn_flags(self.as_ptr() as *const i8, self.len() as raw::STRLEN, 0);
+            SV::from_raw_owned(pthx, svp)
+        }
+    }
+}
+
+/// Create a byte string, without the UTF8 flag.
+impl IntoSV for Vec<u8> {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        (&self[..]).into_sv(pthx)
+    }
+}
+
+macro_rules! os_bytes {
+    ($($ty:ident;)*) => ($(
+        /// Bytes of a byte string, same as `Vec<u8>`.
+        #[cfg(unix)]
+        impl TryFromSV for $ty {
+            type Error = BytesError;
+
+            #[inline]
+            unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                Vec::<u8>::try_from_sv(pthx, raw).map(|v| OsString::from_vec(v).into())
+            }
+
+            #[inline]
+            unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                Vec::<u8>::try_from_sv_nomg(pthx, raw).map(|v| OsString::from_vec(v).into())
+            }
+        }
+
+        /// Create a byte string, without the UTF8 flag.
+        #[cfg(unix)]
+        impl IntoSV for $ty {
+            #[inline]
+            fn into_sv(self, pthx: raw::Interpreter) -> SV {
+                OsStr::as_bytes(self.as_ref()).into_sv(pthx)
+            }
+        }
+    )*)
+}
+
+os_bytes! {
+    OsString;
+    PathBuf;
+}
+
+/// Create a byte string, without the UTF8 flag.
+#[cfg(unix)]
+impl<'a> IntoSV for &'a OsStr {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        self.as_bytes().into_sv(pthx)
+    }
+}
+
+/// Create a byte string, without the UTF8 flag.
+#[cfg(unix)]
+impl<'a> IntoSV for &'a Path {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        self.as_os_str().into_sv(pthx)
+    }
+}
@@ -0,0 +1,241 @@
+//! Rust structs bound to Perl classes.
+//!
+//! The `PerlClass` derive from the `perlxs_derive` crate binds a Rust struct to a Perl package. It
+//! generates a `new` constructor, which takes key-value pairs like a Moose constructor (the struct
+//! must also derive `FromPerlKV`), accessors for fields marked with `#[perlxs(get)]` (read-only)
+//! or `#[perlxs(set)]` (read-write), and a `DESTROY` method.
+//!
+//! Objects are not cloned into new interpreter threads by default: the generated `CLONE_SKIP`
+//! method tells Perl to leave them behind. Structs that implement `Clone` and `Send` can opt into
+//! cloning with the `#[perlxs(clone)]` attribute.
+//!
+//! ```
+//! # #[macro_use] extern crate perl_xs;
+//! # #[macro_use] extern crate perl_sys;
+//! # #[macro_use] extern crate perlxs_derive;
+//! # use perl_xs::IV;
+//! # use perl_xs::class::{Object, ObjectMut};
+//! #[derive(FromPerlKV, PerlClass)]
+//! #[perlxs(package = "Acme::Counter")]
+//! pub struct Counter {
+//!     #[perlxs(set)]
+//!     value: IV,
+//! }
+//!
+//! xs! {
+//!     package Acme::Counter;
+//!     sub get(_ctx, this: Object<Counter>) {
+//!         this.value
+//!     }
+//!     sub inc(_ctx, this: ObjectMut<Counter>) {
+//!         this.value += 1;
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Generated subroutines are registered by adding the struct to the bootstrap block:
+//!
+//! ```
+//! # #[macro_use] extern crate perl_xs;
+//! # #[macro_use] extern crate perl_sys;
+//! # #[macro_use] extern crate perlxs_derive;
+//! # mod acme {
+//! #     use perl_xs::IV;
+//! #     use perl_xs::class::ObjectMut;
+//! #     #[derive(FromPerlKV, PerlClass)]
+//! #     #[perlxs(package = "Acme::Counter")]
+//! #     pub struct Counter {
+//! #         #[perlxs(set)]
+//! #         value: IV,
+//! #     }
+//! #     xs! {
+//! #         package Acme::Counter;
+//! #         sub inc(_ctx, this: ObjectMut<Counter>) {
+//! #             this.value += 1;
+//! #         }
+//! #     }
+//! # }
+//! xs! {
+//!     bootstrap boot_Acme;
+//!     use acme;
+//!     use acme::Counter;
+//! }
+//! # fn main() {}
+//! ```
+
+use std::ops::{Deref, DerefMut};
+
+use crate::context::Context;
+use crate::convert::{get_magic, FromPerlKV, TryFromSV};
+use crate::raw;
+use crate::{DataRef, DataRefMut, SV};
+
+/// Rust type bound to a Perl class.
+///
+/// This trait is usually implemented by `#[derive(PerlClass)]`.
+pub trait Class: Sized + 'static {
+    /// Name of the Perl package.
+    const PACKAGE: &'static str;
+}
+
+/// Invocant of a method of a Rust-backed class.
+///
+/// Conversion fails unless the argument is a reference to an object blessed into `T::PACKAGE` or
+/// one of its subclasses.
+pub struct Object<T: Class>(DataRef<T>);
+
+impl<T: Class> Deref for Object<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Class> TryFromSV for Object<T> {
+    type Error = String;
+
+    unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, raw);
+        Self::try_from_sv_nomg(pthx, raw)
+    }
+
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        check_class::<T>(pthx, raw)?;
+        DataRef::<T>::try_from_sv_nomg(pthx, raw)
+            .map(Object)
+            .map_err(|e| e.to_string())
+    }
+}
+
+/// Invocant of a method of a Rust-backed class, that needs to modify the object.
+///
+/// Same as [`Object`](struct.Object.html), but provides mutable access. Conversion fails if the
+/// object is already in use by another method further up the call stack.
+pub struct ObjectMut<T: Class>(DataRefMut<T>);
+
+impl<T: Class> Deref for ObjectMut<T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: Class> DerefMut for ObjectMut<T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.0
+    }
+}
+
+impl<T: Class> TryFromSV for ObjectMut<T> {
+    type Error = String;
+
+    unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, raw);
+        Self::try_from_sv_nomg(pthx, raw)
+    }
+
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        check_class::<T>(pthx, raw)?;
+        DataRefMut::<T>::try_from_sv_nomg(pthx, raw)
+            .map(ObjectMut)
+            .map_err(|e| e.to_string())
+    }
+}
+
+unsafe fn check_class<T: Class>(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<(), String> {
+    if pthx.sv_isobject(raw) == 0 || !derived_from(pthx, raw, T::PACKAGE) {
+        return Err(format!("not a {} object", T::PACKAGE));
+    }
+    Ok(())
+}
+
+unsafe fn derived_from(pthx: raw::Interpreter, raw: *mut raw::SV, package: &str) -> bool {
+    pthx.sv_derived_from_pvn(raw, package.as_ptr() as *const _, package.len() as _, 0) != 0
+}
+
+#[doc(hidden)]
+pub fn construct<T>(ctx: &mut Context, class: SV) -> SV
+where
+    T: Class + FromPerlKV,
+{
+    let package = class_package::<T>(&class);
+    let value = from_kv::<T>(ctx);
+    ctx.new_sv_with_data(value).bless(&package)
+}
+
+#[doc(hidden)]
+pub fn construct_cloneable<T>(ctx: &mut Context, class: SV) -> SV
+where
+    T: Class + FromPerlKV + Clone + Send,
+{
+    let package = class_package::<T>(&class);
+    let value = from_kv::<T>(ctx);
+    ctx.new_sv_with_cloneable_data(value).bless(&package)
+}
+
+fn class_package<T: Class>(class: &SV) -> String {
+    if !unsafe { derived_from(class.pthx(), class.as_ptr(), T::PACKAGE) } {
+        croak!("{}::new: class is not a subclass of {}", T::PACKAGE, T::PACKAGE);
+    }
+
+    match class.blessed() {
+        Some(package) => package,
+        None => class.to_string().unwrap_or_else(|_| T::PACKAGE.to_owned()),
+    }
+}
+
+fn from_kv<T: FromPerlKV>(ctx: &mut Context) -> T {
+    match T::from_perl_kv(ctx, 1) {
+        Ok(value) => value,
+        Err(e) => croak!("{}", e),
+    }
+}
+
+#[doc(hidden)]
+pub fn destroy<T: Class>(ctx: &mut Context, this: SV) {
+    if ctx.in_global_destruction() {
+        // Other Perl values may be already gone, don't let `T::drop` touch them.
+        if let Some(inner) = this.deref() {
+            inner.forget_data();
+        }
+    }
+}
+
+#[doc(hidden)]
+pub fn read_only(ctx: &mut Context, name: &str) {
+    if ctx.st_items() > 1 {
+        croak!("{} is a read-only accessor", name);
+    }
+}
+
+#[doc(hidden)]
+pub fn invocant<V: TryFromSV>(ctx: &mut Context, name: &str) -> V {
+    match ctx.st_try_fetch::<V>(0) {
+        Some(Ok(v)) => v,
+        Some(Err(e)) => croak!("invalid argument 'this' for {}: {}", name, e),
+        None => croak!("not enough arguments for {}", name),
+    }
+}
+
+#[doc(hidden)]
+pub fn accessor_arg<V: TryFromSV>(ctx: &mut Context, name: &str) -> V {
+    match ctx.st_try_fetch::<V>(1) {
+        Some(Ok(v)) => v,
+        Some(Err(e)) => croak!("invalid value for {}: {}", name, e),
+        None => croak!("not enough arguments for {}", name),
+    }
+}
+
+#[doc(hidden)]
+pub fn accessor_arg_opt<V: TryFromSV>(ctx: &mut Context, name: &str) -> Option<V> {
+    match ctx.st_fetch::<SV>(1) {
+        Some(ref sv) if !sv.ok() => None,
+        _ => Some(accessor_arg(ctx, name)),
+    }
+}
@@ -1,14 +1,35 @@
 //! Context for XS subroutine calls.
-use crate::{AV, SV};
-use crate::convert::{FromSV, IntoSV, TryFromSV};
+use crate::{AV, Bytes, HV, SV, StringWriter};
+use crate::backed;
+use crate::convert::{BigInt, FromSV, IntoSV, Strict, TryFromSV};
 use crate::raw;
 use std;
-use std::ffi::CStr;
+use std::any::Any;
+use std::borrow::Cow;
+use std::convert::TryFrom;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::marker::PhantomData;
+use std::mem::ManuallyDrop;
+use std::os::raw::c_char;
+use std::ops::Deref;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(unix)]
+use std::ffi::{OsStr, OsString};
+#[cfg(unix)]
+use std::path::{Path, PathBuf};
 
 /// XS call context.
 pub struct Context {
     perl: raw::Interpreter,
     stack: raw::Stack,
+    /// Number of leading stack positions filled by `st_store`.
+    stored: isize,
 }
 
 const EMPTY: &'static [i8] = &[0];
@@ -43,6 +64,7 @@ impl Context {
                 let mut ctx = Context {
                     perl: perl,
                     stack: std::mem::uninitialized(),
+                    stored: 0,
                 };
 
                 perl.ouroboros_stack_init(&mut ctx.stack);
@@ -125,6 +147,271 @@ impl Context {
         };
     }
 
+    /// Push value onto Perl stack, assuming there is space for it.
+    ///
+    /// See: [`mPUSHs`](http://perldoc.perl.org/perlapi.html#mPUSHs).
+    #[inline]
+    unsafe fn st_push_unchecked<T>(&mut self, val: T)
+    where
+        T: IntoSV,
+    {
+        let sv = val.into_sv(self.perl);
+        self.perl.ouroboros_stack_push_sv_mortal(&mut self.stack, sv.into_raw());
+    }
+
+    /// Push all values produced by the iterator, extending the stack once for the size hint.
+    fn st_push_iter<I>(&mut self, iter: I)
+    where
+        I: Iterator,
+        I::Item: IntoSV,
+    {
+        let (reserved, _) = iter.size_hint();
+        self.st_extend(reserved);
+        for (i, val) in iter.enumerate() {
+            if i < reserved {
+                unsafe { self.st_push_unchecked(val) };
+            } else {
+                self.st_push(val);
+            }
+        }
+    }
+
+    /// Iterate over all arguments, converting them to `T`.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate perl_xs;
+    /// # #[macro_use] extern crate perl_sys;
+    /// # use perl_xs::NV;
+    /// # xs! {
+    /// #   package Dummy;
+    /// sub sum(ctx) {
+    ///     ctx.st_iter::<NV>().sum::<NV>()
+    /// }
+    /// # }
+    /// # fn main() {}
+    /// ```
+    #[inline]
+    pub fn st_iter<T>(&mut self) -> StackIter<T>
+    where
+        T: FromSV,
+    {
+        StackIter {
+            items: self.st_items(),
+            idx: 0,
+            ctx: self,
+            marker: PhantomData,
+        }
+    }
+
+    /// Make sure there is space for at least `count` more values on the stack.
+    ///
+    /// Pushing values extends the stack as needed, this only avoids repeated reallocations.
+    ///
+    /// See: [`EXTEND`](http://perldoc.perl.org/perlapi.html#EXTEND).
+    #[inline]
+    pub fn st_extend(&mut self, count: usize) {
+        unsafe { self.perl.ouroboros_stack_extend(&mut self.stack, count as raw::SSize_t) };
+    }
+
+    /// Replace the value at position `idx` of the stack, without pushing it.
+    ///
+    /// Positions past the arguments can be set too, one at a time: `idx` can be at most the
+    /// number of values already on the stack, the stack is extended as needed. Together with
+    /// [`st_return`](#method.st_return) this allows transforming the argument list in place.
+    ///
+    /// Panics if `idx` is negative or would leave a gap on the stack.
+    ///
+    /// See: [`ST`](http://perldoc.perl.org/perlapi.html#ST).
+    #[inline]
+    pub fn st_store<T>(&mut self, idx: isize, val: T)
+    where
+        T: IntoSV,
+    {
+        let len = self.st_len();
+        assert!(idx >= 0, "negative stack index {}", idx);
+        assert!(idx <= len, "stack index {} leaves a gap after {} values", idx, len);
+        self.st_extend(idx as usize + 1);
+        let svp = val.into_sv(self.perl).into_mortal();
+        unsafe { self.perl.ouroboros_stack_store(&mut self.stack, idx as raw::SSize_t, svp) };
+        self.stored = std::cmp::max(self.stored, idx + 1);
+    }
+
+    /// Number of leading stack positions that hold values: the arguments or stored values.
+    #[inline]
+    fn st_len(&mut self) -> isize {
+        std::cmp::max(self.st_items(), self.stored)
+    }
+
+    /// Return the first `count` values of the stack.
+    ///
+    /// The values are the arguments, unless replaced with [`st_store`](#method.st_store). Values
+    /// pushed earlier are discarded, values pushed later, including the value returned by the
+    /// subroutine, follow the `count` values.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate perl_xs;
+    /// # #[macro_use] extern crate perl_sys;
+    /// # use perl_xs::IV;
+    /// # xs! {
+    /// #   package Dummy;
+    /// // Double all arguments, reusing the argument list for the result.
+    /// sub double(ctx) {
+    ///     let items = ctx.st_items();
+    ///     for i in 0..items {
+    ///         let v: IV = ctx.st_fetch(i).unwrap();
+    ///         ctx.st_store(i, v * 2);
+    ///     }
+    ///     ctx.st_return(items);
+    /// }
+    /// # }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// Panics if `count` is negative or exceeds the number of values on the stack.
+    ///
+    /// See: [`XSRETURN`](http://perldoc.perl.org/perlapi.html#XSRETURN).
+    pub fn st_return(&mut self, count: isize) {
+        let len = self.st_len();
+        assert!(count >= 0, "negative return count {}", count);
+        assert!(count <= len, "can't return {} values, the stack holds {}", count, len);
+        unsafe {
+            self.perl.ouroboros_stack_prepush(&mut self.stack);
+            for idx in 0..count {
+                let svp = self.perl.ouroboros_stack_fetch(&mut self.stack, idx as raw::SSize_t);
+                self.perl.ouroboros_stack_push_sv(&mut self.stack, svp);
+            }
+        }
+    }
+
+    // SCOPE
+
+    /// Start a new scope for temporary values.
+    ///
+    /// Temporaries created while the scope is alive can be freed early with
+    /// [`Scope::free_tmps`](struct.Scope.html#method.free_tmps), and are freed when the scope is
+    /// dropped, instead of accumulating until the XSUB returns. Values pushed onto the stack are
+    /// temporaries too, so the scope borrows the context and does not allow pushing values: return
+    /// values are pushed after the scope is dropped.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate perl_xs;
+    /// # #[macro_use] extern crate perl_sys;
+    /// # use std::ffi::CString;
+    /// # use perl_xs::{AV, IV, SV, G_DISCARD};
+    /// # xs! {
+    /// #   package Dummy;
+    /// sub process(ctx, rows: AV) {
+    ///     let callback = CString::new("Dummy::callback").unwrap();
+    ///     let mut count = 0;
+    ///     let mut scope = ctx.new_scope();
+    ///     for _row in rows.iter::<SV>() {
+    ///         scope.call_pv(&callback, G_DISCARD);
+    ///         scope.free_tmps();
+    ///         count += 1;
+    ///     }
+    ///     drop(scope);
+    ///     count as IV
+    /// }
+    /// # }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// See: [`ENTER`](http://perldoc.perl.org/perlapi.html#ENTER) and
+    /// [`SAVETMPS`](http://perldoc.perl.org/perlapi.html#SAVETMPS).
+    #[inline]
+    pub fn new_scope(&mut self) -> Scope {
+        unsafe {
+            self.perl.push_scope();
+            self.perl.savetmps();
+        }
+        Scope { ctx: self }
+    }
+
+    /// Prepare to call a Perl code block repeatedly, with `$_` or `$a` and `$b` aliased to
+    /// values.
+    ///
+    /// This is meant for `first { }`, `reduce { }` or `sort { }` style functions. The code
+    /// reference is resolved and `$_`, `$a` and `$b` are localized once, then each call only
+    /// aliases the variables and runs the block. `$a` and `$b` are the variables of the package
+    /// the XSUB is called from, same as for `sort`.
+    ///
+    /// A block written in Perl is called with
+    /// [`MULTICALL`](http://perldoc.perl.org/perlcall.html#Lightweight-Callbacks), like `List::Util`
+    /// does: the sub frame is set up once and each call only runs the ops of the block. Other code
+    /// references, such as XSUBs, are called with
+    /// [`call_sv`](http://perldoc.perl.org/perlapi.html#call_sv). In both cases an exception
+    /// thrown by the block is rethrown unchanged, so `die` with an object keeps the object.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate perl_xs;
+    /// # #[macro_use] extern crate perl_sys;
+    /// # use perl_xs::{AV, SV};
+    /// # xs! {
+    /// #   package Dummy;
+    /// sub first(ctx, block: SV, items: AV) {
+    ///     let mut callback = ctx.new_callback(&block);
+    ///     items.iter::<SV>().flatten().find(|item| callback.call_topic(item).is_true())
+    /// }
+    /// # }
+    /// # fn main() {}
+    /// ```
+    pub fn new_callback(&mut self, block: &SV) -> Callback {
+        let cv = match block.deref() {
+            Some(ref cv) if cv.is_code() => cv.clone(),
+            _ => croak!("not a code reference"),
+        };
+        let mut scope = self.new_scope();
+        let name = |s: &'static [u8]| CStr::from_bytes_with_nul(s).unwrap();
+        let topic = scope.gv(name(b"_\0"), raw::SVt_PV);
+        let a = scope.gv(name(b"a\0"), raw::SVt_PV);
+        let b = scope.gv(name(b"b\0"), raw::SVt_PV);
+        scope.local_sv_slot(topic);
+        scope.local_sv_slot(a);
+        scope.local_sv_slot(b);
+        let multicall = unsafe { scope.ctx.push_multicall(&cv) };
+        Callback {
+            scope: ManuallyDrop::new(scope),
+            cv: cv,
+            multicall: multicall,
+            unwound: false,
+            topic: topic,
+            a: a,
+            b: b,
+        }
+    }
+
+    /// Set up a `MULTICALL` frame for `cv`, unless it is an XSUB.
+    ///
+    /// See: [`PUSH_MULTICALL`](http://perldoc.perl.org/perlcall.html#Lightweight-Callbacks).
+    unsafe fn push_multicall(&mut self, cv: &SV) -> Option<raw::MultiCall> {
+        let cv = cv.as_ptr() as *mut raw::CV;
+        if self.perl.ouroboros_cv_isxsub(cv) {
+            return None;
+        }
+        self.st_putback();
+        let mut multicall: raw::MultiCall = std::mem::uninitialized();
+        self.perl.ouroboros_multicall_push(&mut multicall, cv, raw::G_SCALAR as raw::U8);
+        Some(multicall)
+    }
+
+    /// Call code reference in scalar context without arguments and return a copy of the result.
+    ///
+    /// The result is copied because it is often the pad target of the last op of the block,
+    /// which is overwritten by the next call. Exceptions are rethrown with the original value of
+    /// `$@`.
+    unsafe fn call_sv_scalar(&mut self, cv: *mut raw::SV) -> SV {
+        self.st_putback();
+        self.perl.call_sv(cv, (raw::G_SCALAR | raw::G_NOARGS | raw::G_EVAL) as raw::I32);
+        self.perl.ouroboros_stack_spagain(&mut self.stack);
+        let svp = self.perl.ouroboros_stack_pop_sv(&mut self.stack);
+        self.st_putback();
+        let errsv = self.perl.get_sv(b"@\0".as_ptr() as *const _, raw::GV_ADD as _);
+        if self.perl.sv_true(errsv) != 0 {
+            croak!(raw::Exception(copy_sv(self.perl, errsv)));
+        }
+        SV::from_raw_owned(self.perl, copy_sv(self.perl, svp))
+    }
+
     // XSUB
 
     /// Register new Perl xsub.
@@ -150,6 +437,42 @@ impl Context {
         }
     }
 
+    /// Return the SV of the specified Perl global or package scalar.
+    ///
+    /// See: [`get_sv`](http://perldoc.perl.org/perlapi.html#get_sv).
+    #[inline]
+    pub fn get_sv(&mut self, name: &CStr) -> Option<SV> {
+        let svp = unsafe { self.perl.get_sv(name.as_ptr(), 0) };
+        if svp.is_null() {
+            None
+        } else {
+            Some(unsafe { SV::from_raw_borrowed(self.perl, svp) })
+        }
+    }
+
+    /// Return the SV of the specified Perl global or package scalar, creating it if it does not
+    /// exist.
+    ///
+    /// See: [`get_sv`](http://perldoc.perl.org/perlapi.html#get_sv).
+    #[inline]
+    pub fn get_sv_add(&mut self, name: &CStr) -> SV {
+        unsafe {
+            let svp = self.perl.get_sv(name.as_ptr(), raw::GV_ADD as _);
+            SV::from_raw_borrowed(self.perl, svp)
+        }
+    }
+
+    /// Return true if the interpreter is in global destruction phase.
+    ///
+    /// See: [`${^GLOBAL_PHASE}`](http://perldoc.perl.org/perlvar.html#%24%7B%5EGLOBAL_PHASE%7D).
+    pub fn in_global_destruction(&mut self) -> bool {
+        let name = b"\x07LOBAL_PHASE\0";
+        unsafe {
+            let svp = self.perl.get_sv(name.as_ptr() as *const _, raw::GV_ADD as _);
+            !svp.is_null() && SV::from_raw_borrowed(self.perl, svp).to_vec() == b"DESTRUCT"
+        }
+    }
+
     /// Call subroutine by name.
     ///
     /// See: [`call_pv`](http://perldoc.perl.org/perlapi.html#call_pv).
@@ -169,29 +492,73 @@ impl Context {
         val.into_sv(self.perl)
     }
 
+    /// Create a number if `value` fits in a native integer, a `Math::BigInt` object otherwise.
+    ///
+    /// [`Math::BigInt`](http://perldoc.perl.org/Math/BigInt.html) is loaded on first use. `$@` is
+    /// left unchanged. See also [`BigInt`](../convert/struct.BigInt.html).
+    pub fn new_bigint<T>(&mut self, value: T) -> SV
+    where
+        T: IntoSV + Copy + fmt::Display,
+        IV: TryFrom<T>,
+        UV: TryFrom<T>,
+    {
+        if IV::try_from(value).is_ok() || UV::try_from(value).is_ok() {
+            return self.new_sv(value);
+        }
+        let digits = CString::new(value.to_string()).unwrap();
+        let mut scope = self.new_scope();
+        scope.local_scalar(CStr::from_bytes_with_nul(b"@\0").unwrap());
+        unsafe { scope.ctx.call_bigint_new(&digits) }
+    }
+
+    /// Load `Math::BigInt` unless it is loaded already and call `Math::BigInt->new(digits)`.
+    unsafe fn call_bigint_new(&mut self, digits: &CStr) -> SV {
+        let module = "Math/BigInt.pm";
+        let inc = self.perl.get_hv(b"INC\0".as_ptr() as *const _, 0);
+        if inc.is_null() || self.perl.hv_exists(inc, module.as_ptr() as *const _, module.len() as raw::I32) == 0 {
+            self.perl.require_pv(b"Math/BigInt.pm\0".as_ptr() as *const _);
+            if let Some(err) = eval_error(self.perl) {
+                croak!("can't create Math::BigInt: {}", err);
+            }
+        }
+        let mut argv = [b"Math::BigInt\0".as_ptr() as *mut c_char, digits.as_ptr() as *mut c_char, ptr::null_mut()];
+        self.st_putback();
+        self.perl.call_argv(
+            b"Math::BigInt::new\0".as_ptr() as *const _,
+            (raw::G_SCALAR | raw::G_EVAL) as raw::I32,
+            argv.as_mut_ptr(),
+        );
+        self.perl.ouroboros_stack_spagain(&mut self.stack);
+        let svp = self.perl.ouroboros_stack_pop_sv(&mut self.stack);
+        self.st_putback();
+        if let Some(err) = eval_error(self.perl) {
+            croak!("can't create Math::BigInt: {}", err);
+        }
+        SV::from_raw_borrowed(self.perl, svp)
+    }
+
     /// Create a new SV to store an arbitrary Rust value.
     ///
     /// This function returns a perl reference to a newly allocated SV, that has Rust value attached
     /// via [perl magic](http://perldoc.perl.org/perlguts.html#Magic-Variables).
     ///
-    /// Value can be accessed via `SV::into_data_ref()` method or automatic conversions to
-    /// `DataRef<T>`.
+    /// Value can be accessed via `SV::into_data_ref()` and `SV::into_data_mut()` methods or
+    /// automatic conversions to `DataRef<T>` and `DataRefMut<T>`.
     ///
     /// ```
     /// # #[macro_use] extern crate perl_xs;
     /// # #[macro_use] extern crate perl_sys;
-    /// # use std::cell::RefCell;
-    /// # use perl_xs::{IV, DataRef};
+    /// # use perl_xs::{IV, DataRef, DataRefMut};
     /// xs! {
     ///   package Counter;
     ///   sub new(ctx, class: String, initial: IV) {
-    ///     ctx.new_sv_with_data(RefCell::new(initial)).bless(&class)
+    ///     ctx.new_sv_with_data(initial).bless(&class)
     ///   }
-    ///   sub get(_ctx, this: DataRef<RefCell<IV>>) {
-    ///     return *this.borrow();
+    ///   sub get(_ctx, this: DataRef<IV>) {
+    ///     return *this;
     ///   }
-    ///   sub inc(_ctx, this: DataRef<RefCell<IV>>, amount: Option<IV>) {
-    ///     *this.borrow_mut() += amount.unwrap_or(1);
+    ///   sub inc(_ctx, this: DataRefMut<IV>, amount: Option<IV>) {
+    ///     *this += amount.unwrap_or(1);
     ///   }
     /// }
     /// # fn main() {}
@@ -201,10 +568,93 @@ impl Context {
         self.new_sv(Box::new(value) as Box<dyn std::any::Any>)
     }
 
+    /// Create a new SV to store a Rust value, that is cloned into new interpreter threads.
+    ///
+    /// Same as `new_sv_with_data()`, but the value is attached with `SV::attach_cloneable()`.
+    /// Values created with `new_sv_with_data()` are not available in threads started after the
+    /// value was created.
+    #[inline]
+    pub fn new_sv_with_cloneable_data<T: Clone + Send + 'static>(&mut self, value: T) -> SV {
+        let sv = self.new_sv(0 as raw::IV);
+        sv.attach_cloneable(value);
+        sv.into_ref()
+    }
+
+    /// Create a writer that builds a string directly in the buffer of a new SV.
+    ///
+    /// `capacity` bytes are reserved upfront, the buffer grows as needed.
+    #[inline]
+    pub fn new_string_writer(&mut self, capacity: usize) -> StringWriter {
+        StringWriter::new(self.perl, capacity)
+    }
+
+    /// Create a read-only byte string SV that uses the buffer of `owner` without copying it.
+    ///
+    /// The owner is kept alive until the SV is freed, and shared with SVs cloned into new
+    /// interpreter threads. Copies of the SV made by Perl code are ordinary strings with their own
+    /// buffers. Modifying the SV itself dies with "Modification of a read-only value attempted".
+    ///
+    /// Perl relies on string buffers being terminated with a NUL byte, so the buffer of `owner`
+    /// must end with one. The terminator is not part of the string. Panics if the buffer is not
+    /// terminated.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate perl_xs;
+    /// # #[macro_use] extern crate perl_sys;
+    /// # xs! {
+    /// #   package Dummy;
+    /// sub read_file(ctx, path: String) {
+    ///     let mut data: Vec<u8> = std::fs::read(path).unwrap();
+    ///     data.push(0);
+    ///     ctx.new_sv_backed_by(data)
+    /// }
+    /// # }
+    /// # fn main() {}
+    /// ```
+    #[inline]
+    pub fn new_sv_backed_by<B>(&mut self, owner: B) -> SV
+    where
+        B: AsRef<[u8]> + Send + Sync + 'static,
+    {
+        backed::new_sv(self.perl, owner, |b| b.as_ref(), false)
+    }
+
+    /// Create a read-only character string SV that uses the buffer of `owner` without copying it.
+    ///
+    /// Same as [`new_sv_backed_by`](#method.new_sv_backed_by), but the UTF8 flag is set if the
+    /// string is not ASCII. Works well with `&'static str` lookup tables, with entries like
+    /// `"key\0"`.
+    #[inline]
+    pub fn new_sv_backed_by_str<B>(&mut self, owner: B) -> SV
+    where
+        B: AsRef<str> + Send + Sync + 'static,
+    {
+        let utf8 = !owner.as_ref().is_ascii();
+        backed::new_sv(self.perl, owner, |b| b.as_ref().as_bytes(), utf8)
+    }
+
     /// Return an undefined SV.
     pub fn sv_undef(&mut self) -> SV {
         unsafe { SV::from_raw_owned(self.perl, self.perl.ouroboros_sv_undef()) }
     }
+
+    // ARRAYS AND HASHES
+
+    /// Allocate new empty AV.
+    ///
+    /// See: [`av_make`](http://perldoc.perl.org/perlapi.html#av_make).
+    #[inline]
+    pub fn new_av(&mut self) -> AV {
+        unsafe { AV::from_raw_owned(self.perl, self.perl.av_make(0, std::ptr::null_mut())) }
+    }
+
+    /// Allocate new empty HV.
+    ///
+    /// See: [`newHVhv`](http://perldoc.perl.org/perlapi.html#newHVhv).
+    #[inline]
+    pub fn new_hv(&mut self) -> HV {
+        unsafe { HV::from_raw_owned(self.perl, self.perl.newHVhv(std::ptr::null_mut())) }
+    }
 }
 
 /// Push the value to the perl stack as one or more scalar values.
@@ -239,13 +689,493 @@ where
     }
 }
 
+/// Element type of vectors and slices that are returned as lists.
+///
+/// Implemented for the types that can be converted into a scalar, except for `u8`: `Vec<u8>` and
+/// `&[u8]` are returned as byte strings. Implement this trait for other `IntoSV` types to return
+/// vectors of them, or wrap any iterator in [`List`](struct.List.html) instead.
+pub trait ListItem: IntoSV {}
+
+macro_rules! list_item {
+    ($($ty:ty),* $(,)*) => ($(
+        impl ListItem for $ty {}
+    )*)
+}
+
+list_item! {
+    raw::IV, raw::UV, raw::NV, i8, i16, i32, isize, u16, u32, usize, i128, u128, f32, bool, char,
+    String, Box<str>, Rc<str>, Arc<str>, Bytes, Vec<u8>, SV, AV, HV, Duration, Box<dyn Any>,
+}
+
+#[cfg(unix)]
+list_item! {
+    OsString, PathBuf,
+}
+
+impl<'a> ListItem for &'a str {}
+impl<'a> ListItem for Cow<'a, str> {}
+impl<'a> ListItem for &'a [u8] {}
+impl<'a> ListItem for &'a SV {}
+impl<T: ListItem> ListItem for Option<T> {}
+impl<T: ListItem> ListItem for Strict<T> {}
+impl<T: ListItem + Clone> ListItem for Rc<T> {}
+impl<T: ListItem + Clone> ListItem for Arc<T> {}
+
+#[cfg(unix)]
+impl<'a> ListItem for &'a OsStr {}
+
+#[cfg(unix)]
+impl<'a> ListItem for &'a Path {}
+
+/// Push all elements as individual scalars.
+impl<T> Stackable for Vec<T>
+where
+    T: ListItem,
+{
+    #[inline]
+    fn push_to(self, ctx: &mut Context) {
+        ctx.st_push_iter(self.into_iter());
+    }
+}
+
+/// Push copies of all elements as individual scalars.
+impl<'a, T> Stackable for &'a [T]
+where
+    T: ListItem + Clone,
+{
+    #[inline]
+    fn push_to(self, ctx: &mut Context) {
+        ctx.st_push_iter(self.iter().cloned());
+    }
+}
+
+/// Push a number if the value fits in a native integer, a `Math::BigInt` object otherwise.
+///
+/// See [`Context::new_bigint`](struct.Context.html#method.new_bigint).
+impl<T> Stackable for BigInt<T>
+where
+    T: IntoSV + Copy + fmt::Display,
+    IV: TryFrom<T>,
+    UV: TryFrom<T>,
+{
+    #[inline]
+    fn push_to(self, ctx: &mut Context) {
+        let sv = ctx.new_bigint(self.0);
+        ctx.st_push(sv);
+    }
+}
+
+/// Push all elements as individual numbers or `Math::BigInt` objects.
+impl<T> Stackable for Vec<BigInt<T>>
+where
+    T: IntoSV + Copy + fmt::Display,
+    IV: TryFrom<T>,
+    UV: TryFrom<T>,
+{
+    #[inline]
+    fn push_to(self, ctx: &mut Context) {
+        ctx.st_extend(self.len());
+        for value in self {
+            value.push_to(ctx);
+        }
+    }
+}
+
+/// List of values produced by an iterator.
+///
+/// Wrap any `IntoIterator` in `List` to return its items as a list. The stack is extended once
+/// for the lower bound of the size hint of the iterator, so prefer iterators with exact size
+/// hints.
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use perl_xs::HV;
+/// # use perl_xs::context::List;
+/// # xs! {
+/// #   package Dummy;
+/// sub sorted_keys(_ctx, hv: HV) {
+///     let mut keys: Vec<String> = hv.keys().map(|k| String::from_utf8_lossy(k).into_owned()).collect();
+///     keys.sort();
+///     List(keys.into_iter().rev())
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+pub struct List<I>(pub I);
+
+impl<I> Stackable for List<I>
+where
+    I: IntoIterator,
+    I::Item: IntoSV,
+{
+    #[inline]
+    fn push_to(self, ctx: &mut Context) {
+        ctx.st_push_iter(self.0.into_iter());
+    }
+}
+
+/// Scope for temporary values.
+///
+/// Created by [`Context::new_scope`](struct.Context.html#method.new_scope). The scope borrows the
+/// context until dropped and provides the operations of the context that do not push values onto
+/// the stack: values pushed inside the scope would be freed while still on the stack.
+pub struct Scope<'a> {
+    ctx: &'a mut Context,
+}
+
+impl<'a> Scope<'a> {
+    /// Return the number of arguments, see
+    /// [`Context::st_items`](struct.Context.html#method.st_items).
+    #[inline]
+    pub fn st_items(&mut self) -> isize {
+        self.ctx.st_items()
+    }
+
+    /// Fetch an argument, see [`Context::st_fetch`](struct.Context.html#method.st_fetch).
+    #[inline]
+    pub fn st_fetch<T: FromSV>(&mut self, idx: isize) -> Option<T> {
+        self.ctx.st_fetch(idx)
+    }
+
+    /// Call subroutine by name, see [`Context::call_pv`](struct.Context.html#method.call_pv).
+    #[inline]
+    pub fn call_pv(&mut self, name: &CStr, flags: raw::U32) {
+        self.ctx.call_pv(name, flags)
+    }
+
+    /// Allocate new SV, see [`Context::new_sv`](struct.Context.html#method.new_sv).
+    #[inline]
+    pub fn new_sv<T: IntoSV>(&mut self, val: T) -> SV {
+        self.ctx.new_sv(val)
+    }
+
+    /// Create a new SV to store a Rust value, see
+    /// [`Context::new_sv_with_data`](struct.Context.html#method.new_sv_with_data).
+    #[inline]
+    pub fn new_sv_with_data<T: 'static>(&mut self, value: T) -> SV {
+        self.ctx.new_sv_with_data(value)
+    }
+
+    /// Allocate new empty AV, see [`Context::new_av`](struct.Context.html#method.new_av).
+    #[inline]
+    pub fn new_av(&mut self) -> AV {
+        self.ctx.new_av()
+    }
+
+    /// Allocate new empty HV, see [`Context::new_hv`](struct.Context.html#method.new_hv).
+    #[inline]
+    pub fn new_hv(&mut self) -> HV {
+        self.ctx.new_hv()
+    }
+
+    /// Return a package array, see [`Context::get_av`](struct.Context.html#method.get_av).
+    #[inline]
+    pub fn get_av(&mut self, name: &CStr) -> Option<AV> {
+        self.ctx.get_av(name)
+    }
+
+    /// Return a package scalar, see [`Context::get_sv`](struct.Context.html#method.get_sv).
+    #[inline]
+    pub fn get_sv(&mut self, name: &CStr) -> Option<SV> {
+        self.ctx.get_sv(name)
+    }
+
+    /// Return a package scalar, creating it if it does not exist, see
+    /// [`Context::get_sv_add`](struct.Context.html#method.get_sv_add).
+    #[inline]
+    pub fn get_sv_add(&mut self, name: &CStr) -> SV {
+        self.ctx.get_sv_add(name)
+    }
+
+    /// Free temporaries created since the scope was started.
+    ///
+    /// See: [`FREETMPS`](http://perldoc.perl.org/perlapi.html#FREETMPS).
+    #[inline]
+    pub fn free_tmps(&mut self) {
+        unsafe { self.ctx.perl.free_tmps() };
+    }
+
+    #[inline]
+    fn gv(&mut self, name: &CStr, kind: raw::svtype) -> *mut raw::GV {
+        unsafe { self.ctx.perl.gv_fetchpv(name.as_ptr(), raw::GV_ADD as _, kind) }
+    }
+
+    /// Localize a package scalar, like `local $name` does, and return the new value.
+    ///
+    /// The new value is `undef` and is visible to all code called until the scope is dropped,
+    /// then the previous value is restored. Use `"_"` to localize `$_`.
+    ///
+    /// ```
+    /// # #[macro_use] extern crate perl_xs;
+    /// # #[macro_use] extern crate perl_sys;
+    /// # use std::ffi::CString;
+    /// # use perl_xs::{G_DISCARD, SV};
+    /// # xs! {
+    /// #   package Dummy;
+    /// sub each_topic(ctx, callback: String, items: Vec<String>) {
+    ///     let callback = CString::new(callback).unwrap();
+    ///     let mut scope = ctx.new_scope();
+    ///     let topic = scope.local_scalar(&CString::new("_").unwrap());
+    ///     for item in &items {
+    ///         topic.set_str(item);
+    ///         scope.call_pv(&callback, G_DISCARD);
+    ///     }
+    /// }
+    /// # }
+    /// # fn main() {}
+    /// ```
+    ///
+    /// Perl function: [`save_scalar`](http://perldoc.perl.org/perlapi.html#save_scalar).
+    pub fn local_scalar(&mut self, name: &CStr) -> SV {
+        let gv = self.gv(name, raw::SVt_PV);
+        unsafe { SV::from_raw_borrowed(self.ctx.perl, self.ctx.perl.save_scalar(gv)) }
+    }
+
+    /// Localize a package scalar and make it an alias to `sv`, like `local *name = \$sv` does
+    /// for the scalar slot of the glob.
+    ///
+    /// Changes made through the alias are visible in `sv`.
+    pub fn local_alias(&mut self, name: &CStr, sv: &SV) {
+        let gv = self.gv(name, raw::SVt_PV);
+        self.local_sv_slot(gv);
+        unsafe { alias_scalar(self.ctx.perl, gv, sv) };
+    }
+
+    /// Save the scalar slot of the glob and restore it when the scope is dropped. The slot keeps
+    /// its current value until replaced by [`alias_scalar`](fn.alias_scalar.html).
+    ///
+    /// See: [`SAVEGENERICSV`](http://perldoc.perl.org/perlguts.html#Localizing-changes).
+    #[inline]
+    fn local_sv_slot(&mut self, gv: *mut raw::GV) {
+        unsafe { self.ctx.perl.save_generic_svref(gv_sv_slot(gv)) };
+    }
+
+    /// Localize a package array, like `local @name` does, and return the new empty array.
+    ///
+    /// Perl function: [`save_ary`](http://perldoc.perl.org/perlapi.html#save_ary).
+    pub fn local_array(&mut self, name: &CStr) -> AV {
+        let gv = self.gv(name, raw::SVt_PVAV);
+        unsafe { AV::from_raw_borrowed(self.ctx.perl, self.ctx.perl.save_ary(gv)) }
+    }
+
+    /// Localize a package hash, like `local %name` does, and return the new empty hash.
+    ///
+    /// Perl function: [`save_hash`](http://perldoc.perl.org/perlapi.html#save_hash).
+    pub fn local_hash(&mut self, name: &CStr) -> HV {
+        let gv = self.gv(name, raw::SVt_PVHV);
+        unsafe { HV::from_raw_borrowed(self.ctx.perl, self.ctx.perl.save_hash(gv)) }
+    }
+
+    /// Localize a hash element, like `local $hash{key}` does, and return the new value.
+    ///
+    /// The new value is `undef`. When the scope is dropped the previous value is restored, or
+    /// the element is deleted if it did not exist.
+    ///
+    /// Perl functions: [`save_helem_flags`](http://perldoc.perl.org/perlapi.html#save_helem_flags)
+    /// and [`save_hdelete`](http://perldoc.perl.org/perlapi.html#save_hdelete).
+    pub fn local_helem(&mut self, hv: &HV, key: &str) -> SV {
+        let perl = self.ctx.perl;
+        let exists = hv.exists(key);
+        unsafe {
+            let svp = perl.hv_fetch(hv.as_ptr(), key.as_ptr() as *const _, -(key.len() as raw::I32), 1);
+            if svp.is_null() {
+                croak!("can't localize hash element {}", key);
+            }
+            let keysv = perl.newSVpvn_flags(key.as_ptr() as *const _, key.len() as _, (raw::SVf_UTF8 | raw::SVs_TEMP) as _);
+            if exists {
+                perl.save_helem_flags(hv.as_ptr(), keysv, svp, raw::SAVEf_SETMAGIC as _);
+            } else {
+                perl.save_hdelete(hv.as_ptr(), keysv);
+            }
+            SV::from_raw_borrowed(perl, *svp)
+        }
+    }
+
+    /// Save the current value of `sv` and restore it when the scope is dropped.
+    ///
+    /// Unlike [`local_scalar`](#method.local_scalar), the value is not reset and works for any
+    /// scalar, including array and hash elements.
+    ///
+    /// Perl function: [`save_item`](http://perldoc.perl.org/perlapi.html#save_item).
+    pub fn local_value(&mut self, sv: &SV) {
+        unsafe { self.ctx.perl.save_item(sv.as_ptr()) };
+    }
+}
+
+/// Perl code block prepared for repeated calls.
+///
+/// Created by [`Context::new_callback`](struct.Context.html#method.new_callback). `$_`, `$a`
+/// and `$b` are restored and temporaries are freed when the callback is dropped.
+///
+/// Calls return a copy of the value returned by the block, so results of several calls can be
+/// kept. An exception thrown by the block is passed on to the caller of the XSUB unchanged.
+pub struct Callback<'a> {
+    scope: ManuallyDrop<Scope<'a>>,
+    cv: SV,
+    multicall: Option<raw::MultiCall>,
+    unwound: bool,
+    topic: *mut raw::GV,
+    a: *mut raw::GV,
+    b: *mut raw::GV,
+}
+
+impl<'a> Callback<'a> {
+    /// Call the block with `$_` aliased to `topic` and return its result.
+    ///
+    /// Changes to `$_` made by the block are visible in `topic`.
+    #[inline]
+    pub fn call_topic(&mut self, topic: &SV) -> SV {
+        unsafe {
+            alias_scalar(self.scope.ctx.perl, self.topic, topic);
+            self.call()
+        }
+    }
+
+    /// Call the block with `$a` and `$b` aliased to `a` and `b` and return its result.
+    #[inline]
+    pub fn call_pair(&mut self, a: &SV, b: &SV) -> SV {
+        unsafe {
+            alias_scalar(self.scope.ctx.perl, self.a, a);
+            alias_scalar(self.scope.ctx.perl, self.b, b);
+            self.call()
+        }
+    }
+
+    #[inline]
+    unsafe fn call(&mut self) -> SV {
+        self.scope.free_tmps();
+        let perl = self.scope.ctx.perl;
+        match self.multicall {
+            Some(ref mut multicall) => {
+                // An exception leaves the `MULTICALL` frame by unwinding the interpreter stacks
+                // past the XSUB, so neither the frame nor the scope must be popped afterwards.
+                let svp = match panic::catch_unwind(AssertUnwindSafe(|| perl.ouroboros_multicall_call(multicall))) {
+                    Ok(svp) => svp,
+                    Err(e) => {
+                        self.unwound = true;
+                        panic::resume_unwind(e);
+                    }
+                };
+                SV::from_raw_owned(perl, copy_sv(perl, svp))
+            }
+            None => {
+                let cv = self.cv.as_ptr();
+                self.scope.ctx.call_sv_scalar(cv)
+            }
+        }
+    }
+}
+
+impl<'a> Drop for Callback<'a> {
+    fn drop(&mut self) {
+        if self.unwound {
+            return;
+        }
+        unsafe {
+            if let Some(ref mut multicall) = self.multicall {
+                self.scope.ctx.perl.ouroboros_multicall_pop(multicall);
+            }
+            ManuallyDrop::drop(&mut self.scope);
+        }
+    }
+}
+
+/// Return a new SV with a copy of the value of `svp`, calling get magic.
+unsafe fn copy_sv(perl: raw::Interpreter, svp: *mut raw::SV) -> *mut raw::SV {
+    let copy = perl.newSV(0);
+    perl.sv_setsv_flags(copy, svp, (raw::SV_GMAGIC | raw::SV_NOSTEAL) as _);
+    copy
+}
+
+/// Return the error of the last `eval`, if it failed.
+///
+/// Perl variable: [`ERRSV`](http://perldoc.perl.org/perlapi.html#ERRSV).
+unsafe fn eval_error(perl: raw::Interpreter) -> Option<String> {
+    let errsv = perl.get_sv(b"@\0".as_ptr() as *const _, raw::GV_ADD as _);
+    if perl.sv_true(errsv) == 0 {
+        return None;
+    }
+    let err = SV::from_raw_borrowed(perl, errsv);
+    let msg = match err.as_str() {
+        Ok(msg) => msg.into_owned(),
+        Err(_) => String::from_utf8_lossy(&err.to_vec()).into_owned(),
+    };
+    Some(msg)
+}
+
+/// Make the scalar slot of the glob point to `sv`, like `*gv = \$sv` does.
+///
+/// The slot is replaced in place, so it must be saved with `Scope::local_sv_slot` first.
+pub(crate) unsafe fn alias_scalar(perl: raw::Interpreter, gv: *mut raw::GV, sv: &SV) {
+    let slot = gv_sv_slot(gv);
+    let old = *slot;
+    perl.ouroboros_sv_refcnt_inc_void_nn(sv.as_ptr());
+    *slot = sv.as_ptr();
+    perl.ouroboros_sv_refcnt_dec(old);
+}
+
+/// Return the scalar slot of the glob.
+///
+/// See: [`GvSV`](http://perldoc.perl.org/perlapi.html#GvSV).
+#[inline]
+unsafe fn gv_sv_slot(gv: *mut raw::GV) -> *mut *mut raw::SV {
+    &mut (*(*gv).sv_u.svu_gp).gp_sv
+}
+
+/// See: [`FREETMPS`](http://perldoc.perl.org/perlapi.html#FREETMPS) and
+/// [`LEAVE`](http://perldoc.perl.org/perlapi.html#LEAVE).
+impl<'a> Drop for Scope<'a> {
+    fn drop(&mut self) {
+        unsafe {
+            self.ctx.perl.free_tmps();
+            self.ctx.perl.pop_scope();
+        }
+    }
+}
+
+/// Iterator over subroutine arguments.
+///
+/// Returned by [`Context::st_iter`](struct.Context.html#method.st_iter).
+pub struct StackIter<'a, T> {
+    ctx: &'a mut Context,
+    idx: isize,
+    items: isize,
+    marker: PhantomData<T>,
+}
+
+impl<'a, T> Iterator for StackIter<'a, T>
+where
+    T: FromSV,
+{
+    type Item = T;
+
+    #[inline]
+    fn next(&mut self) -> Option<T> {
+        if self.idx >= self.items {
+            return None;
+        }
+        let val = self.ctx.st_fetch(self.idx);
+        self.idx += 1;
+        val
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = (self.items - self.idx) as usize;
+        (len, Some(len))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for StackIter<'a, T> where T: FromSV {}
+
 macro_rules! impl_tuple {
     (= [$($n:tt $i:tt)*] [$($tails:tt)*]) => (
-        impl<$($n: IntoSV),*> Stackable for ($($n,)*) {
+        impl<$($n: Stackable),*> Stackable for ($($n,)*) {
             #[inline]
             #[allow(unused_variables)]
             fn push_to(self, ctx: &mut Context) {
-                $( ctx.st_push(self.$i); )*
+                $( self.$i.push_to(ctx); )*
             }
         }
         impl_tuple!(> [$($n $i)*] [$($tails)*]);
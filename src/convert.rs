@@ -3,13 +3,35 @@
 use crate::SV;
 use crate::context::Context;
 use crate::error;
+use crate::error::{NumericError, RangeError, ReadOnlyError};
+use crate::primitive::{integer_nomg, Integer};
 use crate::raw;
+use crate::raw::{IV, NV, UV};
+use crate::scalar::decode;
+use std::borrow::Cow;
+use std::convert::TryFrom;
 use std::fmt::Display;
+use std::ops::Deref;
+use std::slice;
 
 /// Fast unsafe conversion from raw SV pointer.
+///
+/// Conversions process get-magic of the SV exactly once, so tied and other magical scalars yield
+/// their current value, and honour operator overloading of objects.
 pub trait FromSV {
     /// Perform the conversion.
     unsafe fn from_sv(perl: raw::Interpreter, raw: *mut raw::SV) -> Self;
+
+    /// Perform the conversion, assuming get-magic has already been processed.
+    ///
+    /// Used by conversions that inspect the SV before converting it, like the one for `Option<T>`.
+    #[inline]
+    unsafe fn from_sv_nomg(perl: raw::Interpreter, raw: *mut raw::SV) -> Self
+    where
+        Self: Sized,
+    {
+        Self::from_sv(perl, raw)
+    }
 }
 
 /// Construct new `SV` from `self`.
@@ -31,11 +53,19 @@ where
 }
 
 /// Attempt unsafe conversion from a raw SV pointer.
+///
+/// Like [`FromSV`](trait.FromSV.html), conversions process get-magic exactly once.
 pub trait TryFromSV: Sized {
     /// The type returned in the event of a conversion error.
     type Error: Display;
     /// Perform the conversion.
     unsafe fn try_from_sv(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error>;
+
+    /// Perform the conversion, assuming get-magic has already been processed.
+    #[inline]
+    unsafe fn try_from_sv_nomg(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        Self::try_from_sv(perl, raw)
+    }
 }
 
 impl<T> TryFromSV for T
@@ -46,6 +76,306 @@ where
     unsafe fn try_from_sv(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<T, Self::Error> {
         Ok(T::from_sv(perl, raw))
     }
+
+    unsafe fn try_from_sv_nomg(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<T, Self::Error> {
+        Ok(T::from_sv_nomg(perl, raw))
+    }
+}
+
+/// Return `Some(v)` if scalar value is defined, `None` otherwise.
+///
+/// Get-magic is processed once, before checking whether the value is defined, so a tied scalar
+/// is fetched only once even if it converts to `Some`.
+impl<T> TryFromSV for Option<T>
+where
+    T: TryFromSV,
+{
+    type Error = T::Error;
+
+    #[inline]
+    unsafe fn try_from_sv(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(perl, raw);
+        Self::try_from_sv_nomg(perl, raw)
+    }
+
+    #[inline]
+    unsafe fn try_from_sv_nomg(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        if perl.ouroboros_sv_ok(raw) != 0 {
+            T::try_from_sv_nomg(perl, raw).map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+}
+
+/// Process get-magic of the SV, if it has any.
+///
+/// Implementations of `FromSV` and `TryFromSV` that inspect the SV directly should call this once
+/// before doing so.
+///
+/// Perl macro: [`SvGETMAGIC`](http://perldoc.perl.org/perlapi.html#SvGETMAGIC).
+#[inline]
+pub unsafe fn get_magic(perl: raw::Interpreter, raw: *mut raw::SV) {
+    if (*raw).sv_flags & raw::SVs_GMG != 0 {
+        perl.mg_get(raw);
+    }
+}
+
+/// Convert a scalar without processing get-magic or operator overloading.
+///
+/// References are converted the way Perl does it when overloading is not in effect: to the
+/// address of the referenced value for numbers, and to a string like `Acme=HASH(0x55d0c8a1e2f8)`
+/// for strings.
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use perl_xs::convert::Raw;
+/// # xs! {
+/// #   package Dummy;
+/// sub describe(_ctx, value: Raw<String>) {
+///     value.0
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct Raw<T>(pub T);
+
+macro_rules! raw_numeric {
+    ($($ty:ty, $conv:ident;)*) => ($(
+        impl FromSV for Raw<$ty> {
+            #[inline]
+            unsafe fn from_sv(perl: raw::Interpreter, raw: *mut raw::SV) -> Self {
+                if perl.ouroboros_sv_rok(raw) != 0 {
+                    Raw(perl.ouroboros_sv_rv(raw) as usize as $ty)
+                } else {
+                    Raw(perl.$conv(raw, 0))
+                }
+            }
+        }
+    )*)
+}
+
+raw_numeric! {
+    raw::IV, sv_2iv_flags;
+    raw::UV, sv_2uv_flags;
+    raw::NV, sv_2nv_flags;
+}
+
+impl TryFromSV for Raw<String> {
+    type Error = std::str::Utf8Error;
+
+    unsafe fn try_from_sv(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        if perl.ouroboros_sv_rok(raw) != 0 {
+            let sv = SV::from_raw_borrowed(perl, raw);
+            let kind = sv.reftype().unwrap_or_default();
+            let addr = sv.refaddr().unwrap_or_default();
+            return Ok(Raw(match sv.blessed() {
+                Some(package) => format!("{}={}(0x{:x})", package, kind, addr),
+                None => format!("{}(0x{:x})", kind, addr),
+            }));
+        }
+        String::try_from_sv_nomg(perl, raw).map(Raw)
+    }
+}
+
+/// Convert a scalar to a number, rejecting values that do not look like one.
+///
+/// Plain numeric conversions follow Perl rules: `"abc"` becomes 0 and `"12abc"` becomes 12, with a
+/// warning at most. `Strict` conversions fail instead if the value is undefined, a reference (even
+/// one with numeric overloading), or a string that is not a number according to
+/// [`looks_like_number`](http://perldoc.perl.org/perlapi.html#looks_like_number). Leading and
+/// trailing whitespace, exponents, `Inf` and `NaN` are accepted, same as in Perl.
+///
+/// Integer types additionally reject numbers with a fractional part, like `"2.5"`, and numbers
+/// out of the type's range, like `"1e30"`, instead of truncating or clamping them.
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use perl_xs::IV;
+/// # use perl_xs::convert::Strict;
+/// # xs! {
+/// #   package Dummy;
+/// sub add_cents(_ctx, total: Strict<IV>, cents: Strict<IV>) {
+///     total.0 + cents.0
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Strict<T>(pub T);
+
+unsafe fn check_numeric(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<(), NumericError> {
+    if perl.ouroboros_sv_ok(raw) == 0 {
+        Err(NumericError::Undef)
+    } else if perl.ouroboros_sv_rok(raw) != 0 {
+        Err(NumericError::Reference)
+    } else if perl.looks_like_number(raw) == 0 {
+        Err(NumericError::NotNumber(value_string(perl, raw)))
+    } else {
+        Ok(())
+    }
+}
+
+unsafe fn value_string(perl: raw::Interpreter, raw: *mut raw::SV) -> String {
+    let mut len = 0;
+    let ptr = perl.sv_2pv_flags(raw, &mut len, 0);
+    let bytes = slice::from_raw_parts(ptr as *const u8, len as usize);
+    decode(bytes, perl.ouroboros_sv_utf8(raw) != 0)
+        .map(Cow::into_owned)
+        .unwrap_or_else(|_| String::from_utf8_lossy(bytes).into_owned())
+}
+
+/// Integer value of a number, rejecting fractions and integers that do not fit in `IV` or `UV`.
+unsafe fn strict_integer(perl: raw::Interpreter, raw: *mut raw::SV, ty: &'static str) -> Result<Integer, NumericError> {
+    let value = integer_nomg(perl, raw);
+    // Perl sets the public IOK flag only if the integer value is exact, otherwise the value was
+    // truncated or clamped.
+    if (*raw).sv_flags & raw::SVf_IOK != 0 {
+        return Ok(value);
+    }
+    let nv = perl.sv_2nv_flags(raw, 0);
+    if nv.trunc() == nv {
+        Err(NumericError::Range(RangeError {
+            value: value_string(perl, raw),
+            ty: ty,
+        }))
+    } else {
+        Err(NumericError::NotInteger(value_string(perl, raw)))
+    }
+}
+
+macro_rules! strict {
+    (@from $perl:ident, $raw:ident, $ty:ident) => (Ok(<$ty>::from_sv_nomg($perl, $raw)));
+    (@try_from $perl:ident, $raw:ident, $ty:ident) => (<$ty>::try_from_sv_nomg($perl, $raw).map_err(NumericError::Range));
+    (@integer $perl:ident, $raw:ident, $ty:ident) => ({
+        let range = |value: String| NumericError::Range(RangeError { value: value, ty: stringify!($ty) });
+        match strict_integer($perl, $raw, stringify!($ty))? {
+            Integer::Signed(v) => $ty::try_from(v).map_err(|_| range(v.to_string())),
+            Integer::Unsigned(v) => $ty::try_from(v).map_err(|_| range(v.to_string())),
+        }
+    });
+    ($($ty:ident, $conv:ident;)*) => ($(
+        impl TryFromSV for Strict<$ty> {
+            type Error = NumericError;
+
+            #[inline]
+            unsafe fn try_from_sv(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                get_magic(perl, raw);
+                Self::try_from_sv_nomg(perl, raw)
+            }
+
+            #[inline]
+            unsafe fn try_from_sv_nomg(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                check_numeric(perl, raw)?;
+                strict!(@$conv perl, raw, $ty).map(Strict)
+            }
+        }
+    )*)
+}
+
+strict! {
+    IV, integer;
+    UV, integer;
+    NV, from;
+    i8, integer;
+    i16, integer;
+    i32, integer;
+    isize, integer;
+    u8, integer;
+    u16, integer;
+    u32, integer;
+    usize, integer;
+    f32, try_from;
+}
+
+impl<T: IntoSV> IntoSV for Strict<T> {
+    #[inline]
+    fn into_sv(self, perl: raw::Interpreter) -> SV {
+        self.0.into_sv(perl)
+    }
+}
+
+/// Integer passed to Perl as a `Math::BigInt` object if it does not fit in a native integer.
+///
+/// Plain `i128` and `u128` values that do not fit are passed as decimal strings instead, which
+/// Perl treats as floating point numbers in arithmetic. Use this wrapper to keep them exact.
+/// [`Math::BigInt`](http://perldoc.perl.org/Math/BigInt.html) is loaded when needed.
+///
+/// Creating the object calls into Perl, so `BigInt` is not `IntoSV`: return it from an XSUB,
+/// alone or in a tuple or `Vec`, or create the value with
+/// [`Context::new_bigint`](../struct.Context.html#method.new_bigint).
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use perl_xs::convert::BigInt;
+/// # xs! {
+/// #   package Dummy;
+/// sub factorial(_ctx, n: u32) {
+///     BigInt((1..=n as u128).product::<u128>())
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BigInt<T>(pub T);
+
+/// Argument that aliases the variable passed by the caller.
+///
+/// Perl passes arguments by reference, so assigning to an argument with one of the setters of
+/// [`SV`](../struct.SV.html) modifies the caller's variable, the same way assigning to `$_[0]`
+/// does. `Alias` makes that intent explicit: it fails if the argument is read-only, before the
+/// subroutine body runs. Get-magic is not processed, as the value is not read.
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use perl_xs::IV;
+/// # use perl_xs::convert::Alias;
+/// # xs! {
+/// #   package Dummy;
+/// // my $n = read_into(my $buf, 10);
+/// sub read_into(_ctx, buf: Alias, len: IV) {
+///     buf.set_str(&"x".repeat(len as usize));
+///     len
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+pub struct Alias(pub SV);
+
+impl Alias {
+    /// Return the aliased SV.
+    #[inline]
+    pub fn into_inner(self) -> SV {
+        self.0
+    }
+}
+
+impl Deref for Alias {
+    type Target = SV;
+
+    #[inline]
+    fn deref(&self) -> &SV {
+        &self.0
+    }
+}
+
+impl TryFromSV for Alias {
+    type Error = ReadOnlyError;
+
+    #[inline]
+    unsafe fn try_from_sv(perl: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        let sv = SV::from_raw_borrowed(perl, raw);
+        if sv.is_readonly() {
+            Err(ReadOnlyError)
+        } else {
+            Ok(Alias(sv))
+        }
+    }
 }
 
 /// Construct new `Self` from `key value pairs of the XSUB context`.
@@ -0,0 +1,667 @@
+//! Rust values attached to Perl scalars.
+//!
+//! Values are stored in `PERL_MAGIC_ext` magic entries, one entry per value. Each entry records
+//! the name of the value's type, so that several values of different types can be attached to one
+//! SV and found by type. Entries created by other extensions built with perl-xs are recognized
+//! too, but never handed out: types with the same name may have different layout in a different
+//! binary.
+//!
+//! When a new interpreter thread is created, values attached with
+//! [`attach_cloneable`](../struct.SV.html#method.attach_cloneable) are cloned into the new thread.
+//! Other values are not available in the new thread: an attempt to access them results in an
+//! error.
+
+use std::any::{self, Any, TypeId};
+use std::cell::Cell;
+use std::ffi::CStr;
+use std::mem::ManuallyDrop;
+use std::ops::{Deref, DerefMut};
+use std::os::raw::{c_char, c_int};
+use std::panic;
+use std::ptr;
+use std::slice;
+use std::sync::Arc;
+
+use crate::convert::{get_magic, IntoSV, TryFromSV};
+use crate::error::DataError;
+use crate::raw;
+use crate::SV;
+
+/// Borrow state value of a slot that is borrowed mutably.
+const EXCLUSIVE: isize = -1;
+
+/// Value of `mg_private` in magic entries created by perl-xs.
+const MAGIC_ID: u16 = 0x5853;
+
+/// Name of the type of the stored value.
+///
+/// Layout of this struct must not change: it is read by other extensions.
+#[repr(C)]
+struct SlotKey {
+    ptr: *const u8,
+    len: usize,
+}
+
+impl SlotKey {
+    fn new(key: &'static str) -> SlotKey {
+        SlotKey {
+            ptr: key.as_ptr(),
+            len: key.len(),
+        }
+    }
+
+    unsafe fn as_bytes(&self) -> &[u8] {
+        slice::from_raw_parts(self.ptr, self.len)
+    }
+}
+
+/// Function that copies the value for a new interpreter thread.
+type DupFn = fn(&dyn Any) -> Box<dyn Any>;
+
+fn dup_clone<T: Clone + Send + 'static>(value: &dyn Any) -> Box<dyn Any> {
+    let value = value.downcast_ref::<T>().expect("dup function matches the value type");
+    Box::new(value.clone())
+}
+
+/// Rust value stored in the SV magic together with its borrow state.
+#[repr(C)]
+struct Slot {
+    /// Must be the first field, see `SlotKey`.
+    key: SlotKey,
+    /// Type of the value, known even if the value is not available in this thread.
+    type_id: TypeId,
+    /// Number of live `DataRef`s or `EXCLUSIVE` if there is a `DataRefMut`.
+    borrow: Cell<isize>,
+    dup: Option<DupFn>,
+    /// `None` if the value was not cloned into this thread.
+    value: Option<Box<dyn Any>>,
+}
+
+impl SV {
+    /// Store a Rust value inside the SV.
+    ///
+    /// SV takes ownership of the `value`, which will be dropped when the SV reference count drops
+    /// to zero. It is possible to attach multiple values to the SV, `into_data_ref` returns the
+    /// first one. Use [`attach`](#method.attach) to store values that can be looked up by type.
+    ///
+    /// This relies on [Perl magic](http://perldoc.perl.org/perlguts.html#Magic-Virtual-Tables) to
+    /// store the value. Magic is not copied on scalar assignment, so to be useful, scalars with
+    /// magic need to be passed around by reference.
+    pub fn add_data(&self, value: Box<dyn Any>) {
+        let type_id = Any::type_id(&*value);
+        self.add_slot(any::type_name::<dyn Any>(), type_id, value, None);
+    }
+
+    fn add_slot(&self, key: &'static str, type_id: TypeId, value: Box<dyn Any>, dup: Option<DupFn>) {
+        let pthx = self.pthx();
+        let svp = self.as_ptr();
+        let obj = ptr::null_mut();
+        let slot = Slot {
+            key: SlotKey::new(key),
+            type_id: type_id,
+            borrow: Cell::new(0),
+            dup: dup,
+            value: Some(value),
+        };
+        let ptr = Box::into_raw(Box::new(slot)) as *mut c_char;
+        if let Some(magic) = self.find_empty_magic() {
+            unsafe { (*magic).mg_ptr = ptr };
+            return;
+        }
+        let len = 0;
+        unsafe {
+            let magic = pthx.sv_magicext(svp, obj, raw::PERL_MAGIC_ext as _, &VTBL_ANY, ptr, len);
+            (*magic).mg_private = MAGIC_ID;
+            (*magic).mg_flags |= raw::MGf_DUP as raw::U8;
+        }
+    }
+
+    /// Attach a value of type `T` to the SV.
+    ///
+    /// Values of different types can be attached to the same SV and retrieved by type with
+    /// [`attached`](#method.attached), [`attached_mut`](#method.attached_mut) or by converting a
+    /// reference to the SV into `DataRef<T>`. Attaching a value replaces the previously attached
+    /// value of the same type.
+    ///
+    /// The value is not available in new interpreter threads, see
+    /// [`attach_cloneable`](#method.attach_cloneable).
+    ///
+    /// Panics if the previous value of the same type is currently borrowed.
+    pub fn attach<T: 'static>(&self, value: T) {
+        self.attach_with(value, None);
+    }
+
+    /// Attach a value of type `T` to the SV, cloning it into new interpreter threads.
+    ///
+    /// To share one value between threads instead, attach an `Arc`, see
+    /// [`attach_shared`](#method.attach_shared).
+    pub fn attach_cloneable<T: Clone + Send + 'static>(&self, value: T) {
+        self.attach_with(value, Some(dup_clone::<T>));
+    }
+
+    /// Attach a value shared between interpreter threads.
+    ///
+    /// The value is wrapped into `Arc` and can be retrieved as `DataRef<Arc<T>>`.
+    pub fn attach_shared<T: Send + Sync + 'static>(&self, value: T) {
+        self.attach_cloneable(Arc::new(value));
+    }
+
+    fn attach_with<T: 'static>(&self, value: T, dup: Option<DupFn>) {
+        match self.find_slot_of::<T>() {
+            Some(slot) => unsafe {
+                if (*slot).borrow.get() != 0 {
+                    croak!("can't replace attached {}: value is borrowed", any::type_name::<T>());
+                }
+                (*slot).value = Some(Box::new(value));
+                (*slot).dup = dup;
+            },
+            None => self.add_slot(any::type_name::<T>(), TypeId::of::<T>(), Box::new(value), dup),
+        }
+    }
+
+    /// Get a reference to the value of type `T` attached to the SV.
+    ///
+    /// `None` is returned if there is no such value, or if the value is borrowed mutably.
+    pub fn attached<T: 'static>(&self) -> Option<DataRef<T>> {
+        self.clone().try_into_data_ref_of::<T>().ok()
+    }
+
+    /// Get a mutable reference to the value of type `T` attached to the SV.
+    ///
+    /// `None` is returned if there is no such value, or if the value is borrowed.
+    pub fn attached_mut<T: 'static>(&self) -> Option<DataRefMut<T>> {
+        self.clone().try_into_data_mut_of::<T>().ok()
+    }
+
+    /// Remove the value of type `T` from the SV and return it.
+    ///
+    /// The emptied magic entry is reused by the next `attach`, so repeated attaching and detaching
+    /// does not grow the SV's magic chain.
+    ///
+    /// `None` is returned if there is no such value, or if the value is borrowed.
+    pub fn detach<T: 'static>(&self) -> Option<T> {
+        let magic = self.find_magic(|slot| slot.type_id == TypeId::of::<T>())?;
+        unsafe {
+            let slot = (*magic).mg_ptr as *mut Slot;
+            if (*slot).borrow.get() != 0 || (*slot).value.is_none() {
+                return None;
+            }
+            (*magic).mg_ptr = ptr::null_mut();
+            let slot = Box::from_raw(slot);
+            slot.value
+                .and_then(|value| value.downcast::<T>().ok())
+                .map(|value| *value)
+        }
+    }
+
+    /// Return true if a value of type `T` was attached to the SV by another extension.
+    ///
+    /// Such values can not be accessed: the type with the same name may be defined differently in
+    /// the other extension.
+    pub fn has_foreign_data<T: 'static>(&self) -> bool {
+        let key = any::type_name::<T>().as_bytes();
+        let pthx = self.pthx();
+        unsafe {
+            let mut magic = pthx.mg_find(self.as_ptr(), raw::PERL_MAGIC_ext as _);
+            while !magic.is_null() {
+                if is_foreign_slot(magic) && (*((*magic).mg_ptr as *const SlotKey)).as_bytes() == key {
+                    return true;
+                }
+                magic = (*magic).mg_moremagic;
+            }
+        }
+        false
+    }
+
+    /// Get a reference to a Rust value stored in the SV.
+    ///
+    /// `Ref` takes ownership of the SV to protect the value from being dropped while reference is
+    /// alive.
+    ///
+    /// `None` is returned if no value was found in the SV, or if the value is currently borrowed
+    /// mutably.
+    pub fn into_data_ref(self) -> Option<DataRef<dyn Any>> {
+        self.try_into_data_ref().ok()
+    }
+
+    /// Get a mutable reference to a Rust value stored in the SV.
+    ///
+    /// `None` is returned if no value was found in the SV, or if the value is currently borrowed.
+    pub fn into_data_mut(self) -> Option<DataRefMut<dyn Any>> {
+        self.try_into_data_mut().ok()
+    }
+
+    fn try_into_data_ref(self) -> Result<DataRef<dyn Any>, DataError> {
+        let slot = self.find_slot().ok_or(DataError::InvalidValue)?;
+        unsafe { borrow_slot(self, slot) }
+    }
+
+    fn try_into_data_mut(self) -> Result<DataRefMut<dyn Any>, DataError> {
+        let slot = self.find_slot().ok_or(DataError::InvalidValue)?;
+        unsafe { borrow_slot_mut(self, slot) }
+    }
+
+    fn try_into_data_ref_of<T: 'static>(self) -> Result<DataRef<T>, DataError> {
+        let slot = self.find_slot_of::<T>().ok_or(DataError::InvalidValue)?;
+        let data = unsafe { borrow_slot(self, slot)? };
+        Ok(data.downcast().expect("slot holds a value of the requested type"))
+    }
+
+    fn try_into_data_mut_of<T: 'static>(self) -> Result<DataRefMut<T>, DataError> {
+        let slot = self.find_slot_of::<T>().ok_or(DataError::InvalidValue)?;
+        let data = unsafe { borrow_slot_mut(self, slot)? };
+        Ok(data.downcast().expect("slot holds a value of the requested type"))
+    }
+
+    fn find_slot(&self) -> Option<*mut Slot> {
+        self.find_magic(|_| true)
+            .map(|magic| unsafe { (*magic).mg_ptr as *mut Slot })
+    }
+
+    fn find_slot_of<T: 'static>(&self) -> Option<*mut Slot> {
+        self.find_magic(|slot| slot.type_id == TypeId::of::<T>())
+            .map(|magic| unsafe { (*magic).mg_ptr as *mut Slot })
+    }
+
+    /// Find the first magic entry created by this extension, that holds a value matching `pred`.
+    fn find_magic<F>(&self, pred: F) -> Option<*mut raw::MAGIC>
+    where
+        F: Fn(&Slot) -> bool,
+    {
+        let pthx = self.pthx();
+        unsafe {
+            let mut magic = pthx.mg_findext(self.as_ptr(), raw::PERL_MAGIC_ext as _, &VTBL_ANY);
+            while !magic.is_null() {
+                if is_own_slot(magic) && pred(&*((*magic).mg_ptr as *const Slot)) {
+                    return Some(magic);
+                }
+                magic = (*magic).mg_moremagic;
+            }
+        }
+        None
+    }
+
+    /// Find a magic entry created by this extension, whose value was detached.
+    ///
+    /// Such entries are reused when a new value is attached.
+    fn find_empty_magic(&self) -> Option<*mut raw::MAGIC> {
+        let pthx = self.pthx();
+        unsafe {
+            let mut magic = pthx.mg_findext(self.as_ptr(), raw::PERL_MAGIC_ext as _, &VTBL_ANY);
+            while !magic.is_null() {
+                if is_empty_slot(magic) {
+                    return Some(magic);
+                }
+                magic = (*magic).mg_moremagic;
+            }
+        }
+        None
+    }
+
+    /// Detach Rust value stored in the SV without dropping it.
+    ///
+    /// Used during global destruction, when the value's destructor can no longer safely access
+    /// other Perl values.
+    pub(crate) fn forget_data(&self) {
+        let pthx = self.pthx();
+        unsafe {
+            let mut magic = pthx.mg_findext(self.as_ptr(), raw::PERL_MAGIC_ext as _, &VTBL_ANY);
+            while !magic.is_null() {
+                if is_own_slot(magic) {
+                    (*magic).mg_ptr = ptr::null_mut();
+                }
+                magic = (*magic).mg_moremagic;
+            }
+        }
+    }
+}
+
+unsafe fn is_own_slot(magic: *const raw::MAGIC) -> bool {
+    (*magic).mg_type as u8 == raw::PERL_MAGIC_ext as u8
+        && (*magic).mg_virtual as *const raw::MGVTBL == &VTBL_ANY as *const raw::MGVTBL
+        && !(*magic).mg_ptr.is_null()
+}
+
+unsafe fn is_empty_slot(magic: *const raw::MAGIC) -> bool {
+    (*magic).mg_type as u8 == raw::PERL_MAGIC_ext as u8
+        && (*magic).mg_virtual as *const raw::MGVTBL == &VTBL_ANY as *const raw::MGVTBL
+        && (*magic).mg_ptr.is_null()
+}
+
+/// Return true if the magic entry was created by perl-xs in another extension.
+///
+/// `mg_private` alone is not enough: other extensions may use the same value. The entry must also
+/// have the vtable of `VTBL_ANY`'s shape, and the arguments `add_slot` uses.
+unsafe fn is_foreign_slot(magic: *const raw::MAGIC) -> bool {
+    let vtbl = (*magic).mg_virtual as *const raw::MGVTBL;
+    (*magic).mg_type as u8 == raw::PERL_MAGIC_ext as u8
+        && (*magic).mg_private == MAGIC_ID
+        && !vtbl.is_null()
+        && vtbl != &VTBL_ANY as *const raw::MGVTBL
+        && is_any_vtbl(&*vtbl)
+        && (*magic).mg_obj.is_null()
+        && (*magic).mg_len == 0
+        && !(*magic).mg_ptr.is_null()
+}
+
+/// Return true if the vtable defines the same callbacks as `VTBL_ANY`.
+fn is_any_vtbl(vtbl: &raw::MGVTBL) -> bool {
+    vtbl.svt_get.is_none()
+        && vtbl.svt_set.is_none()
+        && vtbl.svt_len.is_none()
+        && vtbl.svt_clear.is_none()
+        && vtbl.svt_free.is_some()
+        && vtbl.svt_copy.is_none()
+        && vtbl.svt_dup.is_some()
+        && vtbl.svt_local.is_none()
+}
+
+unsafe fn borrow_slot(owner: SV, slot: *mut Slot) -> Result<DataRef<dyn Any>, DataError> {
+    let inner: *const dyn Any = match (*slot).value {
+        Some(ref value) => &**value,
+        None => return Err(DataError::NotCloned { class: None }),
+    };
+    let borrow = (*slot).borrow.get();
+    if borrow == EXCLUSIVE {
+        return Err(DataError::Borrowed {
+            class: None,
+            mutably: true,
+        });
+    }
+    (*slot).borrow.set(borrow + 1);
+    Ok(DataRef {
+        inner: inner,
+        slot: slot,
+        owner: owner,
+    })
+}
+
+unsafe fn borrow_slot_mut(owner: SV, slot: *mut Slot) -> Result<DataRefMut<dyn Any>, DataError> {
+    let inner: *mut dyn Any = match (*slot).value {
+        Some(ref mut value) => &mut **value,
+        None => return Err(DataError::NotCloned { class: None }),
+    };
+    let borrow = (*slot).borrow.get();
+    if borrow != 0 {
+        return Err(DataError::Borrowed {
+            class: None,
+            mutably: borrow == EXCLUSIVE,
+        });
+    }
+    (*slot).borrow.set(EXCLUSIVE);
+    Ok(DataRefMut {
+        inner: inner,
+        slot: slot,
+        owner: owner,
+    })
+}
+
+pthx! {
+    fn magic_free_any(_pthx, _sv: *mut raw::SV, magic: *mut raw::MAGIC) -> c_int {
+        unsafe {
+            let ptr = (*magic).mg_ptr as *mut Slot;
+            if !ptr.is_null() {
+                drop(Box::from_raw(ptr));
+            }
+        }
+        0
+    }
+}
+
+pthx! {
+    fn magic_dup_any(_pthx, magic: *mut raw::MAGIC, _param: *mut raw::CLONE_PARAMS) -> c_int {
+        unsafe {
+            let old = (*magic).mg_ptr as *const Slot;
+            if !old.is_null() {
+                let value = match ((*old).dup, &(*old).value) {
+                    (Some(dup), &Some(ref value)) => panic::catch_unwind(panic::AssertUnwindSafe(|| dup(&**value))).ok(),
+                    _ => None,
+                };
+                let slot = Slot {
+                    key: SlotKey {
+                        ptr: (*old).key.ptr,
+                        len: (*old).key.len,
+                    },
+                    type_id: (*old).type_id,
+                    borrow: Cell::new(0),
+                    dup: (*old).dup,
+                    value: value,
+                };
+                (*magic).mg_ptr = Box::into_raw(Box::new(slot)) as *mut c_char;
+            }
+        }
+        0
+    }
+}
+
+static VTBL_ANY: raw::MGVTBL = raw::MGVTBL {
+    svt_free: Some(magic_free_any),
+    svt_dup: Some(magic_dup_any),
+    ..raw::EMPTY_MGVTBL
+};
+
+impl IntoSV for Box<dyn Any> {
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        let sv = unsafe { SV::from_raw_owned(pthx, pthx.newSV(0)) };
+        sv.add_data(self);
+        sv.into_ref()
+    }
+}
+
+/// Return name of the package the referenced object is blessed into.
+unsafe fn class_name(pthx: raw::Interpreter, rv: *mut raw::SV) -> Option<String> {
+    if pthx.sv_isobject(rv) == 0 {
+        return None;
+    }
+    let name = pthx.sv_reftype(pthx.ouroboros_sv_rv(rv), 1);
+    Some(CStr::from_ptr(name).to_string_lossy().into_owned())
+}
+
+/// Reference to a Rust value stored inside the SV.
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use perl_xs::{SV, DataRef, convert::TryFromSV};
+/// # xs! {
+/// #   package Dummy;
+/// #   sub foo(ctx) {
+/// let value_sv: SV = ctx.new_sv_with_data(String::from("Hello world!"));
+/// let data_ref: DataRef<String> = value_sv.into_data_ref().and_then(DataRef::downcast).unwrap();
+/// assert_eq!(&**data_ref, "Hello world!");
+/// #   }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// Because Perl scalars are always shared, `DataRef` can provide only immutable references to the
+/// stored value. If mutable access is needed, use [`DataRefMut`](struct.DataRefMut.html).
+///
+/// `DataRef` can be used as a type for a subroutine parameter:
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use std::cell::Cell;
+/// # use perl_xs::{IV, DataRef};
+/// # xs! {
+/// #   package Dummy;
+/// sub get(_ctx, value: DataRef<Cell<IV>>) {
+///     value.get()
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+///
+/// If `get` is then called with a scalar that does not contain a `Cell<IV>` value, perl exception
+/// will be thrown.
+///
+/// Any number of `DataRef`s to the same value can exist at the same time, but none can be created
+/// while the value is borrowed by a `DataRefMut`.
+pub struct DataRef<T: ?Sized> {
+    inner: *const T,
+    slot: *const Slot,
+    owner: SV,
+}
+
+impl DataRef<dyn Any> {
+    /// Attempt to downcast the ref to the concrete type while preserving owning SV.
+    pub fn downcast<T: 'static>(self) -> Option<DataRef<T>> {
+        let inner = match unsafe { (*self.inner).downcast_ref::<T>() } {
+            Some(r) => r as *const T,
+            None => return None,
+        };
+        let this = ManuallyDrop::new(self);
+        Some(DataRef {
+            inner: inner,
+            slot: this.slot,
+            owner: unsafe { ptr::read(&this.owner) },
+        })
+    }
+}
+
+impl<T: ?Sized> Deref for DataRef<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.inner }
+    }
+}
+
+impl<T: ?Sized> Drop for DataRef<T> {
+    fn drop(&mut self) {
+        unsafe {
+            let borrow = &(*self.slot).borrow;
+            borrow.set(borrow.get() - 1);
+        }
+    }
+}
+
+impl TryFromSV for DataRef<dyn Any> {
+    type Error = DataError;
+
+    unsafe fn try_from_sv(pthx: raw::Interpreter, sv: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, sv);
+        Self::try_from_sv_nomg(pthx, sv)
+    }
+
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, sv: *mut raw::SV) -> Result<Self, Self::Error> {
+        let outer = SV::from_raw_borrowed(pthx, sv);
+        let inner = outer.deref().ok_or(DataError::NotReference)?;
+        inner
+            .try_into_data_ref()
+            .map_err(|e| e.with_class(class_name(pthx, sv)))
+    }
+}
+
+impl<T: 'static> TryFromSV for DataRef<T> {
+    type Error = DataError;
+
+    unsafe fn try_from_sv(pthx: raw::Interpreter, svp: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, svp);
+        Self::try_from_sv_nomg(pthx, svp)
+    }
+
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, svp: *mut raw::SV) -> Result<Self, Self::Error> {
+        let outer = SV::from_raw_borrowed(pthx, svp);
+        let inner = outer.deref().ok_or(DataError::NotReference)?;
+        inner
+            .try_into_data_ref_of::<T>()
+            .map_err(|e| e.with_class(class_name(pthx, svp)))
+    }
+}
+
+/// Mutable reference to a Rust value stored inside the SV.
+///
+/// Only one `DataRefMut` to a value can exist at a time, and only while there are no `DataRef`s to
+/// the same value. Conversion of a subroutine parameter to a `DataRefMut` fails with a Perl
+/// exception naming the class and the subroutine, if the value is already borrowed (for example,
+/// when a Perl callback calls back into a method of the same object):
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use perl_xs::{IV, DataRefMut};
+/// # xs! {
+/// #   package Dummy;
+/// sub increment(_ctx, value: DataRefMut<IV>, amount: IV) {
+///     *value += amount;
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+pub struct DataRefMut<T: ?Sized> {
+    inner: *mut T,
+    slot: *const Slot,
+    owner: SV,
+}
+
+impl DataRefMut<dyn Any> {
+    /// Attempt to downcast the ref to the concrete type while preserving owning SV.
+    pub fn downcast<T: 'static>(self) -> Option<DataRefMut<T>> {
+        let inner = match unsafe { (*self.inner).downcast_mut::<T>() } {
+            Some(r) => r as *mut T,
+            None => return None,
+        };
+        let this = ManuallyDrop::new(self);
+        Some(DataRefMut {
+            inner: inner,
+            slot: this.slot,
+            owner: unsafe { ptr::read(&this.owner) },
+        })
+    }
+}
+
+impl<T: ?Sized> Deref for DataRefMut<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        unsafe { &*self.inner }
+    }
+}
+
+impl<T: ?Sized> DerefMut for DataRefMut<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        unsafe { &mut *self.inner }
+    }
+}
+
+impl<T: ?Sized> Drop for DataRefMut<T> {
+    fn drop(&mut self) {
+        unsafe { (*self.slot).borrow.set(0) };
+    }
+}
+
+impl TryFromSV for DataRefMut<dyn Any> {
+    type Error = DataError;
+
+    unsafe fn try_from_sv(pthx: raw::Interpreter, sv: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, sv);
+        Self::try_from_sv_nomg(pthx, sv)
+    }
+
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, sv: *mut raw::SV) -> Result<Self, Self::Error> {
+        let outer = SV::from_raw_borrowed(pthx, sv);
+        let inner = outer.deref().ok_or(DataError::NotReference)?;
+        inner
+            .try_into_data_mut()
+            .map_err(|e| e.with_class(class_name(pthx, sv)))
+    }
+}
+
+impl<T: 'static> TryFromSV for DataRefMut<T> {
+    type Error = DataError;
+
+    unsafe fn try_from_sv(pthx: raw::Interpreter, svp: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, svp);
+        Self::try_from_sv_nomg(pthx, svp)
+    }
+
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, svp: *mut raw::SV) -> Result<Self, Self::Error> {
+        let outer = SV::from_raw_borrowed(pthx, svp);
+        let inner = outer.deref().ok_or(DataError::NotReference)?;
+        inner
+            .try_into_data_mut_of::<T>()
+            .map_err(|e| e.with_class(class_name(pthx, svp)))
+    }
+}
@@ -1,6 +1,6 @@
 //! Misc errors
 
-use std::fmt;
+use std::{fmt, str};
 
 /// Error instantiating a rust struct from a perl stack
 #[derive(Debug)]
@@ -86,3 +86,152 @@ impl fmt::Display for ToStructErr {
         write!(f, "")
     }
 }
+
+/// Error accessing a Rust value stored inside a Perl scalar
+#[derive(Debug)]
+pub enum DataError {
+    /// The value is not a reference
+    NotReference,
+    /// The referenced scalar does not contain a Rust value of the requested type
+    InvalidValue,
+    /// The Rust value is already borrowed in a way that conflicts with the requested access
+    Borrowed {
+        /// Name of the package the object is blessed into, if any
+        class: Option<String>,
+        /// True if the conflicting borrow is mutable
+        mutably: bool,
+    },
+    /// The Rust value was not cloned when the current interpreter thread was created
+    NotCloned {
+        /// Name of the package the object is blessed into, if any
+        class: Option<String>,
+    },
+}
+
+impl DataError {
+    pub(crate) fn with_class(self, name: Option<String>) -> Self {
+        match self {
+            DataError::Borrowed { mutably, .. } => DataError::Borrowed {
+                class: name,
+                mutably: mutably,
+            },
+            DataError::NotCloned { .. } => DataError::NotCloned { class: name },
+            e => e,
+        }
+    }
+}
+
+impl fmt::Display for DataError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            DataError::NotReference => write!(f, "not a reference"),
+            DataError::InvalidValue => write!(f, "invalid value"),
+            DataError::Borrowed { ref class, mutably } => {
+                match *class {
+                    Some(ref name) => write!(f, "{} object", name)?,
+                    None => write!(f, "value")?,
+                }
+                if mutably {
+                    write!(f, " is already borrowed mutably")
+                } else {
+                    write!(f, " is already borrowed")
+                }
+            }
+            DataError::NotCloned { ref class } => {
+                match *class {
+                    Some(ref name) => write!(f, "{} object", name)?,
+                    None => write!(f, "value")?,
+                }
+                write!(f, " is not available in this thread")
+            }
+        }
+    }
+}
+
+/// Error converting a character string into a byte string
+#[derive(Debug)]
+pub enum BytesError {
+    /// Offset of the first character that does not fit in a byte
+    WideChar(usize),
+    /// The string has the UTF8 flag on, but its buffer is not valid UTF-8
+    Malformed(str::Utf8Error),
+}
+
+impl fmt::Display for BytesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            BytesError::WideChar(offset) => write!(f, "wide character at offset {}", offset),
+            BytesError::Malformed(ref e) => write!(f, "malformed UTF-8 in character string: {}", e),
+        }
+    }
+}
+
+/// Error converting a scalar into a type with a narrower range of values
+#[derive(Debug)]
+pub struct RangeError {
+    /// Value of the scalar, as a string
+    pub value: String,
+    /// Name of the type the value was converted to
+    pub ty: &'static str,
+}
+
+impl fmt::Display for RangeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "value {} out of range for {}", self.value, self.ty)
+    }
+}
+
+/// Error converting a scalar into a single character
+#[derive(Debug)]
+pub enum CharError {
+    /// The string is not valid UTF-8
+    Utf8(str::Utf8Error),
+    /// The string does not consist of exactly one character
+    Length(usize),
+}
+
+impl fmt::Display for CharError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            CharError::Utf8(ref e) => e.fmt(f),
+            CharError::Length(len) => write!(f, "expected a single character, got {}", len),
+        }
+    }
+}
+
+/// Error converting a scalar that does not look like a number
+#[derive(Debug)]
+pub enum NumericError {
+    /// The value is undefined
+    Undef,
+    /// The value is a reference
+    Reference,
+    /// The value is a string that does not look like a number
+    NotNumber(String),
+    /// The value is a number, but not an integer
+    NotInteger(String),
+    /// The number does not fit in the requested type
+    Range(RangeError),
+}
+
+impl fmt::Display for NumericError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            NumericError::Undef => write!(f, "undef is not a number"),
+            NumericError::Reference => write!(f, "reference is not a number"),
+            NumericError::NotNumber(ref s) => write!(f, "'{}' is not a number", s),
+            NumericError::NotInteger(ref s) => write!(f, "'{}' is not an integer", s),
+            NumericError::Range(ref e) => e.fmt(f),
+        }
+    }
+}
+
+/// Error aliasing a read-only argument, like a literal constant
+#[derive(Debug)]
+pub struct ReadOnlyError;
+
+impl fmt::Display for ReadOnlyError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "modification of a read-only value attempted")
+    }
+}
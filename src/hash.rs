@@ -3,7 +3,7 @@ use std::mem;
 use std::slice::from_raw_parts;
 
 use crate::SV;
-use crate::convert::{FromSV, TryFromSV};
+use crate::convert::{get_magic, FromSV, IntoSV, TryFromSV};
 use crate::handle::Owned;
 use crate::raw;
 
@@ -14,7 +14,7 @@ impl HV {
     fn pthx(&self) -> raw::Interpreter {
         self.0.pthx()
     }
-    fn as_ptr(&self) -> *mut raw::HV {
+    pub(crate) fn as_ptr(&self) -> *mut raw::HV {
         self.0.as_ptr()
     }
 
@@ -128,6 +128,11 @@ impl TryFromSV for HV {
     type Error = &'static str;
 
     unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<HV, Self::Error> {
+        get_magic(pthx, raw);
+        Self::try_from_sv_nomg(pthx, raw)
+    }
+
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<HV, Self::Error> {
         if pthx.ouroboros_sv_rok(raw) == 0 {
             return Err("not a hash reference");
         }
@@ -139,6 +144,19 @@ impl TryFromSV for HV {
     }
 }
 
+/// Create a new reference to the hash.
+impl IntoSV for HV {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        assert!(self.pthx() == pthx);
+        unsafe {
+            let raw = self.as_ptr();
+            self.0.incref();
+            SV::from_raw_owned(pthx, pthx.newRV_noinc(raw as *mut _))
+        }
+    }
+}
+
 pub struct Iter<'a, T> {
     hv: &'a HV,
     ty: PhantomData<T>,
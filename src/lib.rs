@@ -15,20 +15,35 @@ pub mod raw;
 mod handle;
 
 mod scalar;
+mod bytes;
+mod primitive;
+mod backed;
+mod pinned;
+mod writer;
+mod data;
 mod array;
 mod hash;
+pub mod class;
 pub mod context;
 pub mod convert;
 pub mod error;
+pub mod magic;
+pub mod meta;
+pub mod overload;
+pub mod tie;
 
 #[doc(hidden)]
 pub mod croak;
 
 pub use crate::array::AV;
+pub use crate::bytes::Bytes;
 pub use crate::context::Context;
 pub use crate::convert::FromPerlKV;
 pub use crate::hash::HV;
+pub use crate::pinned::PinnedStr;
 pub use crate::raw::{G_DISCARD, G_VOID};
 pub use crate::raw::{SSize_t, Size_t, IV, NV, STRLEN, UV};
-pub use crate::scalar::{DataRef, SV};
+pub use crate::data::{DataRef, DataRefMut};
+pub use crate::scalar::SV;
+pub use crate::writer::StringWriter;
 
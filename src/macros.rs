@@ -15,6 +15,10 @@
 /// # fn main() {}
 /// ```
 ///
+/// Doc comments on subroutines are recorded together with parameter names and types in the
+/// `PERL_XS_SIGNATURES` table, see the [`meta`](meta/index.html) module. The table is also
+/// returned by the `__xs_signatures` class method generated for each package.
+///
 /// Second form is used to generate bootstrap function used by Perl to intialize XS module. Each
 /// crate should contain exactly one invocation in this form:
 ///
@@ -31,62 +35,132 @@
 ///
 /// Function name given to `bootstrap` keyword must start with `boot_` followed by the Perl module
 /// name.
+///
+/// Each module listed with `use` in the bootstrap block must define a `PERL_XS` table of
+/// subroutines to register. It is generated by `xs!`, [`tie!`](macro.tie.html),
+/// [`overload!`](macro.overload.html) and `#[derive(PerlClass)]`, or can be written by hand.
+///
+/// Modules listed with `boot` must also define a `PERL_XS_BOOT` table of functions to run once
+/// all subroutines are registered. Only [`overload!`](macro.overload.html) generates one:
+///
+/// ```
+/// #[macro_use] extern crate perl_xs;
+/// #[macro_use] extern crate perl_sys;
+/// # mod acme { xs! { package Acme; } }
+/// # mod ops { overload! { package Acme; fallback = true; } }
+/// xs! {
+///     bootstrap boot_Acme;
+///     use acme;
+///     use ops;
+///     boot ops;
+/// }
+/// # fn main() {}
+/// ```
 #[macro_export]
 macro_rules! xs {
+    // Internal rule: define a single XSUB.
+    (
+        @sub $pkg:path,
+        $( #[doc = $doc:expr] )*
+        fn $name:ident ($ctx:ident $(, $par:ident : $pty:ty )* ) $body:block
+    ) => (
+        pthx! {
+            $( #[doc = $doc] )*
+            #[allow(unused_mut, non_snake_case)]
+            fn $name (pthx, _cv: *mut $crate::raw::CV) {
+                let perl = $crate::raw::initialize(pthx);
+                $crate::context::Context::wrap(perl, |mut $ctx| {
+                    let mut _arg = 0;
+                    $(
+                        let mut $par = match $ctx.st_try_fetch::<$pty>(_arg) {
+                            Some(Ok(v)) => v,
+                            Some(Err(e)) =>
+                                croak!(
+                                    concat!(
+                                        "invalid argument '",
+                                        stringify!($par),
+                                        "' for ",
+                                        stringify!($pkg),
+                                        "::",
+                                        stringify!($name),
+                                        ": {}"),
+                                    e),
+                            None =>
+                                croak!(
+                                    concat!(
+                                        "not enough arguments for ",
+                                        stringify!($pkg),
+                                        "::",
+                                        stringify!($name))),
+                        };
+                        _arg += 1;
+                    )*
+                    $body
+                });
+            }
+        }
+    );
+
     (
         package $pkg:path ;
-        $( sub $name:ident ($ctx:ident $(, $par:ident : $pty:ty )* ) $body:block )*
+        $(
+            $( #[doc = $doc:expr] )*
+            sub $name:ident ($ctx:ident $(, $par:ident : $pty:ty )* ) $body:block
+        )*
     ) => (
         $(
-            pthx! {
-                #[allow(unused_mut)]
-                fn $name (pthx, _cv: *mut $crate::raw::CV) {
-                    let perl = $crate::raw::initialize(pthx);
-                    $crate::context::Context::wrap(perl, |mut $ctx| {
-                        let mut _arg = 0;
-                        $(
-                            let $par = match $ctx.st_try_fetch::<$pty>(_arg) {
-                                Some(Ok(v)) => v,
-                                Some(Err(e)) =>
-                                    croak!(
-                                        concat!(
-                                            "invalid argument '",
-                                            stringify!($par),
-                                            "' for ",
-                                            stringify!($pkg),
-                                            "::",
-                                            stringify!($name),
-                                            ": {}"),
-                                        e),
-                                None =>
-                                    croak!(
-                                        concat!(
-                                            "not enough arguments for ",
-                                            stringify!($pkg),
-                                            "::",
-                                            stringify!($name))),
-                            };
-                            _arg += 1;
-                        )*
-                        $body
-                    });
-                }
+            xs! {
+                @sub $pkg,
+                $( #[doc = $doc] )*
+                fn $name ($ctx $(, $par : $pty )* ) $body
             }
         )*
 
+        pthx! {
+            fn __xs_signatures (pthx, _cv: *mut $crate::raw::CV) {
+                let perl = $crate::raw::initialize(pthx);
+                $crate::context::Context::wrap(perl, |ctx| {
+                    $crate::meta::signatures_to_sv(ctx, PERL_XS_SIGNATURES)
+                });
+            }
+        }
+
+        pub const PERL_XS_SIGNATURES: &'static [$crate::meta::Signature] = &[
+            $(
+                $crate::meta::Signature {
+                    package: stringify!($pkg),
+                    name: stringify!($name),
+                    params: &[
+                        $(
+                            $crate::meta::Param {
+                                name: stringify!($par),
+                                ty: stringify!($pty),
+                            }
+                        ),*
+                    ],
+                    doc: &[ $( $doc ),* ],
+                }
+            ),*
+        ];
+
         pub const PERL_XS: &'static [ (&'static str, $crate::raw::XSUBADDR_t) ] = &[
             $(
                 (
                     concat!(stringify!($pkg), "::", stringify!($name)),
                     $name as $crate::raw::XSUBADDR_t,
-                )
-            ),*
+                ),
+            )*
+            (
+                concat!(stringify!($pkg), "::__xs_signatures"),
+                __xs_signatures as $crate::raw::XSUBADDR_t,
+            ),
         ];
     );
 
     (
         bootstrap $boot:ident;
         $( use $( $name:ident )::+ ; )*
+        $( boot $( $bname:ident )::+ ; )*
     ) => (
         pthx! {
             #[no_mangle]
@@ -101,6 +175,12 @@ macro_rules! xs {
                         }
                     )*
 
+                    $(
+                        for boot in $( $bname )::*::PERL_XS_BOOT {
+                            boot(ctx);
+                        }
+                    )*
+
                     1 as $crate::raw::IV
                 });
             }
@@ -108,6 +188,305 @@ macro_rules! xs {
     );
 }
 
+/// Define methods of a tied hash, array or scalar class.
+///
+/// The type must implement [`TieHash`](tie/trait.TieHash.html),
+/// [`TieArray`](tie/trait.TieArray.html) or [`TieScalar`](tie/trait.TieScalar.html)
+/// respectively. Like the package form of `xs!`, there should be only one invocation per Rust
+/// module, and the module should be added to the bootstrap block.
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// mod counter {
+///     use perl_xs::{Context, IV, SV};
+///     use perl_xs::tie::TieScalar;
+///
+///     pub struct Counter(IV);
+///
+///     impl TieScalar for Counter {
+///         type Value = IV;
+///         fn tie(_ctx: &mut Context, _args: Vec<SV>) -> Result<Self, String> { Ok(Counter(0)) }
+///         fn fetch(&self) -> IV { self.0 }
+///         fn store(&mut self, value: IV) { self.0 = value; }
+///     }
+///
+///     tie! {
+///         package Acme::Counter;
+///         scalar Counter;
+///     }
+/// }
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! tie {
+    (
+        package $pkg:path ;
+        hash $ty:ty ;
+    ) => (
+        xs! {
+            @sub $pkg,
+            fn TIEHASH(ctx, class: String) {
+                $crate::tie::tie(
+                    ctx,
+                    class,
+                    concat!(stringify!($pkg), "::TIEHASH"),
+                    <$ty as $crate::tie::TieHash>::tie)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn FETCH(_ctx, this: $crate::DataRef<$ty>, key: String) {
+                $crate::tie::TieHash::fetch(&*this, &key)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn STORE(_ctx,
+                     this: $crate::DataRefMut<$ty>,
+                     key: String,
+                     value: <$ty as $crate::tie::TieHash>::Value) {
+                $crate::tie::TieHash::store(&mut *this, key, value)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn EXISTS(_ctx, this: $crate::DataRef<$ty>, key: String) {
+                $crate::tie::TieHash::exists(&*this, &key)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn DELETE(_ctx, this: $crate::DataRefMut<$ty>, key: String) {
+                $crate::tie::TieHash::delete(&mut *this, &key)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn CLEAR(_ctx, this: $crate::DataRefMut<$ty>) {
+                $crate::tie::TieHash::clear(&mut *this)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn FIRSTKEY(_ctx, this: $crate::DataRefMut<$ty>) {
+                $crate::tie::TieHash::first_key(&mut *this)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn NEXTKEY(_ctx, this: $crate::DataRefMut<$ty>, last: String) {
+                $crate::tie::TieHash::next_key(&mut *this, &last)
+            }
+        }
+
+        tie!(@table $pkg, TIEHASH FETCH STORE EXISTS DELETE CLEAR FIRSTKEY NEXTKEY);
+    );
+
+    (
+        package $pkg:path ;
+        array $ty:ty ;
+    ) => (
+        xs! {
+            @sub $pkg,
+            fn TIEARRAY(ctx, class: String) {
+                $crate::tie::tie(
+                    ctx,
+                    class,
+                    concat!(stringify!($pkg), "::TIEARRAY"),
+                    <$ty as $crate::tie::TieArray>::tie)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn FETCH(_ctx, this: $crate::DataRef<$ty>, idx: $crate::IV) {
+                $crate::tie::TieArray::fetch(&*this, idx as usize)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn STORE(_ctx,
+                     this: $crate::DataRefMut<$ty>,
+                     idx: $crate::IV,
+                     value: <$ty as $crate::tie::TieArray>::Value) {
+                $crate::tie::TieArray::store(&mut *this, idx as usize, value)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn FETCHSIZE(_ctx, this: $crate::DataRef<$ty>) {
+                $crate::tie::TieArray::fetch_size(&*this) as $crate::IV
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn STORESIZE(_ctx, this: $crate::DataRefMut<$ty>, size: $crate::IV) {
+                $crate::tie::TieArray::store_size(&mut *this, size as usize)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn EXTEND(_ctx, this: $crate::DataRefMut<$ty>, size: $crate::IV) {
+                $crate::tie::TieArray::extend(&mut *this, size as usize)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn EXISTS(_ctx, this: $crate::DataRef<$ty>, idx: $crate::IV) {
+                $crate::tie::TieArray::exists(&*this, idx as usize)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn DELETE(_ctx, this: $crate::DataRefMut<$ty>, idx: $crate::IV) {
+                $crate::tie::TieArray::delete(&mut *this, idx as usize)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn CLEAR(_ctx, this: $crate::DataRefMut<$ty>) {
+                $crate::tie::TieArray::clear(&mut *this)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn PUSH(ctx, this: $crate::DataRefMut<$ty>) {
+                let values = $crate::tie::rest_args(ctx, 1, concat!(stringify!($pkg), "::PUSH"));
+                $crate::tie::TieArray::push(&mut *this, values)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn POP(_ctx, this: $crate::DataRefMut<$ty>) {
+                $crate::tie::TieArray::pop(&mut *this)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn SHIFT(_ctx, this: $crate::DataRefMut<$ty>) {
+                $crate::tie::TieArray::shift(&mut *this)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn UNSHIFT(ctx, this: $crate::DataRefMut<$ty>) {
+                let values = $crate::tie::rest_args(ctx, 1, concat!(stringify!($pkg), "::UNSHIFT"));
+                $crate::tie::TieArray::unshift(&mut *this, values)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn SPLICE(ctx, this: $crate::DataRefMut<$ty>) {
+                $crate::tie::splice(ctx, &mut *this, concat!(stringify!($pkg), "::SPLICE"))
+            }
+        }
+
+        tie!(@table $pkg,
+             TIEARRAY FETCH STORE FETCHSIZE STORESIZE EXTEND EXISTS DELETE CLEAR
+             PUSH POP SHIFT UNSHIFT SPLICE);
+    );
+
+    (
+        package $pkg:path ;
+        scalar $ty:ty ;
+    ) => (
+        xs! {
+            @sub $pkg,
+            fn TIESCALAR(ctx, class: String) {
+                $crate::tie::tie(
+                    ctx,
+                    class,
+                    concat!(stringify!($pkg), "::TIESCALAR"),
+                    <$ty as $crate::tie::TieScalar>::tie)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn FETCH(_ctx, this: $crate::DataRef<$ty>) {
+                $crate::tie::TieScalar::fetch(&*this)
+            }
+        }
+        xs! {
+            @sub $pkg,
+            fn STORE(_ctx, this: $crate::DataRefMut<$ty>, value: <$ty as $crate::tie::TieScalar>::Value) {
+                $crate::tie::TieScalar::store(&mut *this, value)
+            }
+        }
+
+        tie!(@table $pkg, TIESCALAR FETCH STORE);
+    );
+
+    (@table $pkg:path, $( $name:ident )*) => (
+        pub const PERL_XS: &'static [ (&'static str, $crate::raw::XSUBADDR_t) ] = &[
+            $(
+                (
+                    concat!(stringify!($pkg), "::", stringify!($name)),
+                    $name as $crate::raw::XSUBADDR_t,
+                ),
+            )*
+        ];
+    );
+}
+
+/// Define overloaded operators for a Perl package.
+///
+/// Each entry maps an operator, as accepted by
+/// [`use overload`](http://perldoc.perl.org/overload.html), to a subroutine defined the same way
+/// as in `xs!`. The optional `fallback` line sets the fallback mode: `true` lets Perl derive
+/// missing operators from conversion operators, `false` disables it. See the
+/// [`overload`](overload/index.html) module for an example.
+///
+/// Like the package form of `xs!`, there should be only one invocation per Rust module, and the
+/// module should be added to the bootstrap block both with `use` and `boot`, so that the fallback
+/// mode is set when the module is loaded.
+#[macro_export]
+macro_rules! overload {
+    (
+        package $pkg:path ;
+        $( fallback = $fallback:expr ; )?
+        $(
+            $( #[doc = $doc:expr] )*
+            $op:literal => sub $name:ident ($ctx:ident $(, $par:ident : $pty:ty )* ) $body:block
+        )*
+    ) => (
+        $(
+            xs! {
+                @sub $pkg,
+                $( #[doc = $doc] )*
+                fn $name ($ctx $(, $par : $pty )* ) $body
+            }
+        )*
+
+        xs! {
+            @sub $pkg,
+            fn __overload_nil(_ctx) {}
+        }
+
+        fn __overload_boot(_ctx: &mut $crate::context::Context) {
+            $( $crate::overload::set_fallback(_ctx, stringify!($pkg), $fallback); )?
+        }
+
+        pub const PERL_XS: &'static [ (&'static str, $crate::raw::XSUBADDR_t) ] = &[
+            (
+                concat!(stringify!($pkg), "::(("),
+                __overload_nil as $crate::raw::XSUBADDR_t,
+            ),
+            (
+                concat!(stringify!($pkg), "::()"),
+                __overload_nil as $crate::raw::XSUBADDR_t,
+            ),
+            $(
+                (
+                    concat!(stringify!($pkg), "::(", $op),
+                    $name as $crate::raw::XSUBADDR_t,
+                ),
+            )*
+        ];
+
+        pub const PERL_XS_BOOT: &'static [fn(&mut $crate::context::Context)] = &[__overload_boot];
+    );
+}
+
 /// Throw a perl exception.
 ///
 /// Perl exceptions are implemented as panics in Rust, but do not call the panic hook - user
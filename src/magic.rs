@@ -0,0 +1,243 @@
+//! Scalar magic implemented by Rust callbacks.
+//!
+//! [`ScalarMagic`](struct.ScalarMagic.html) collects callbacks that Perl runs when a magical
+//! scalar is read, assigned to, cleared or freed. Attach it to a scalar with
+//! [`SV::add_magic`](../struct.SV.html#method.add_magic) to turn the scalar into a live view of
+//! Rust state:
+//!
+//! ```
+//! # #[macro_use] extern crate perl_xs;
+//! # #[macro_use] extern crate perl_sys;
+//! # use std::ffi::CString;
+//! # use std::sync::Arc;
+//! # use std::sync::atomic::{AtomicIsize, Ordering};
+//! # use perl_xs::IV;
+//! # use perl_xs::magic::ScalarMagic;
+//! xs! {
+//!     package Acme;
+//!     sub init(ctx) {
+//!         let counter = Arc::new(AtomicIsize::new(0));
+//!         let setter = counter.clone();
+//!         ctx.get_sv_add(&CString::new("Acme::counter").unwrap()).add_magic(
+//!             ScalarMagic::new()
+//!                 .get(move || counter.load(Ordering::SeqCst) as IV)
+//!                 .set(move |value: IV| setter.store(value as isize, Ordering::SeqCst)),
+//!         );
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Panics in callbacks are turned into Perl exceptions, same as panics in XSUBs.
+
+use std::os::raw::{c_char, c_int};
+use std::panic::AssertUnwindSafe;
+use std::ptr;
+
+use crate::convert::{IntoSV, TryFromSV};
+use crate::raw;
+use crate::SV;
+
+type GetFn = dyn FnMut(raw::Interpreter) -> SV;
+type SetFn = dyn FnMut(raw::Interpreter, *mut raw::SV);
+
+/// Set of Rust callbacks run on access to a magical scalar.
+///
+/// All callbacks are optional.
+#[derive(Default)]
+pub struct ScalarMagic {
+    get: Option<Box<GetFn>>,
+    set: Option<Box<SetFn>>,
+    len: Option<Box<dyn FnMut() -> usize>>,
+    clear: Option<Box<dyn FnMut()>>,
+    free: Option<Box<dyn FnMut()>>,
+}
+
+impl ScalarMagic {
+    /// Create an empty set of callbacks.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the callback run before the scalar is read.
+    ///
+    /// Value returned by the callback is stored in the scalar and seen by the reader.
+    pub fn get<F, T>(mut self, mut get: F) -> Self
+    where
+        F: FnMut() -> T + 'static,
+        T: IntoSV,
+    {
+        self.get = Some(Box::new(move |pthx| get().into_sv(pthx)));
+        self
+    }
+
+    /// Set the callback run after a value is assigned to the scalar.
+    ///
+    /// Perl exception is thrown if the new value can not be converted to `T`.
+    pub fn set<F, T>(mut self, mut set: F) -> Self
+    where
+        F: FnMut(T) + 'static,
+        T: TryFromSV,
+    {
+        self.set = Some(Box::new(move |pthx, sv| {
+            match unsafe { T::try_from_sv(pthx, sv) } {
+                Ok(value) => set(value),
+                Err(e) => croak!("invalid value: {}", e),
+            }
+        }));
+        self
+    }
+
+    /// Set the callback that returns the length of the value.
+    pub fn len<F>(mut self, len: F) -> Self
+    where
+        F: FnMut() -> usize + 'static,
+    {
+        self.len = Some(Box::new(len));
+        self
+    }
+
+    /// Set the callback run when the scalar is cleared.
+    pub fn clear<F>(mut self, clear: F) -> Self
+    where
+        F: FnMut() + 'static,
+    {
+        self.clear = Some(Box::new(clear));
+        self
+    }
+
+    /// Set the callback run when the scalar is freed or the magic is removed.
+    pub fn free<F>(mut self, free: F) -> Self
+    where
+        F: FnMut() + 'static,
+    {
+        self.free = Some(Box::new(free));
+        self
+    }
+}
+
+impl SV {
+    /// Attach Rust callbacks to the scalar.
+    ///
+    /// Callbacks are dropped when the scalar is freed. Like other magic, it is not copied on
+    /// assignment: the variable the magic is attached to stays magical, but copies of its value
+    /// are plain scalars.
+    ///
+    /// Callbacks are not cloned into new interpreter threads: the scalar in the new thread keeps
+    /// its last value, but does not run any callbacks.
+    ///
+    /// Perl function: [`sv_magicext`](http://perldoc.perl.org/perlapi.html#sv_magicext).
+    pub fn add_magic(&self, magic: ScalarMagic) {
+        let ptr = Box::into_raw(Box::new(magic)) as *const c_char;
+        unsafe {
+            let magic = self.pthx().sv_magicext(
+                self.as_ptr(),
+                ptr::null_mut(),
+                raw::PERL_MAGIC_ext as _,
+                &VTBL_SCALAR,
+                ptr,
+                0,
+            );
+            (*magic).mg_flags |= raw::MGf_DUP as raw::U8;
+        }
+    }
+}
+
+/// Return the callbacks, or `None` in a new thread, where they were detached by `magic_dup`.
+unsafe fn callbacks<'a>(magic: *mut raw::MAGIC) -> Option<&'a mut ScalarMagic> {
+    ((*magic).mg_ptr as *mut ScalarMagic).as_mut()
+}
+
+pthx! {
+    fn magic_get(pthx, sv: *mut raw::SV, magic: *mut raw::MAGIC) -> c_int {
+        let perl = raw::initialize(pthx);
+        unsafe {
+            raw::catch_unwind(perl, AssertUnwindSafe(|| {
+                if let Some(get) = callbacks(magic).and_then(|c| c.get.as_mut()) {
+                    let value = get(perl);
+                    perl.sv_setsv_flags(sv, value.as_ptr(), 0);
+                }
+            }));
+        }
+        0
+    }
+}
+
+pthx! {
+    fn magic_set(pthx, sv: *mut raw::SV, magic: *mut raw::MAGIC) -> c_int {
+        let perl = raw::initialize(pthx);
+        unsafe {
+            raw::catch_unwind(perl, AssertUnwindSafe(|| {
+                if let Some(set) = callbacks(magic).and_then(|c| c.set.as_mut()) {
+                    set(perl, sv);
+                }
+            }));
+        }
+        0
+    }
+}
+
+pthx! {
+    fn magic_len(pthx, _sv: *mut raw::SV, magic: *mut raw::MAGIC) -> raw::U32 {
+        let perl = raw::initialize(pthx);
+        unsafe {
+            raw::catch_unwind(perl, AssertUnwindSafe(|| {
+                match callbacks(magic).and_then(|c| c.len.as_mut()) {
+                    Some(len) => len() as raw::U32,
+                    None => 0,
+                }
+            }))
+        }
+    }
+}
+
+pthx! {
+    fn magic_clear(pthx, _sv: *mut raw::SV, magic: *mut raw::MAGIC) -> c_int {
+        let perl = raw::initialize(pthx);
+        unsafe {
+            raw::catch_unwind(perl, AssertUnwindSafe(|| {
+                if let Some(clear) = callbacks(magic).and_then(|c| c.clear.as_mut()) {
+                    clear();
+                }
+            }));
+        }
+        0
+    }
+}
+
+pthx! {
+    fn magic_free(pthx, _sv: *mut raw::SV, magic: *mut raw::MAGIC) -> c_int {
+        let perl = raw::initialize(pthx);
+        unsafe {
+            let ptr = (*magic).mg_ptr as *mut ScalarMagic;
+            if !ptr.is_null() {
+                (*magic).mg_ptr = ptr::null_mut();
+                let mut callbacks = Box::from_raw(ptr);
+                raw::catch_unwind(perl, AssertUnwindSafe(move || {
+                    if let Some(ref mut free) = callbacks.free {
+                        free();
+                    }
+                }));
+            }
+        }
+        0
+    }
+}
+
+pthx! {
+    fn magic_dup(_pthx, magic: *mut raw::MAGIC, _param: *mut raw::CLONE_PARAMS) -> c_int {
+        // Closures can not be cloned: detach them in the new thread instead of sharing the box.
+        unsafe { (*magic).mg_ptr = ptr::null_mut() };
+        0
+    }
+}
+
+static VTBL_SCALAR: raw::MGVTBL = raw::MGVTBL {
+    svt_get: Some(magic_get),
+    svt_set: Some(magic_set),
+    svt_len: Some(magic_len),
+    svt_clear: Some(magic_clear),
+    svt_free: Some(magic_free),
+    svt_dup: Some(magic_dup),
+    ..raw::EMPTY_MGVTBL
+};
@@ -0,0 +1,150 @@
+//! Introspectable metadata for subroutines defined with the `xs!` macro.
+//!
+//! Each `xs!` package block records the signatures of its subroutines in a `PERL_XS_SIGNATURES`
+//! table next to `PERL_XS`. The same table is available to Perl code at runtime via the generated
+//! `__xs_signatures` class method:
+//!
+//! ```perl
+//! for my $sub (@{ Acme->__xs_signatures }) {
+//!     printf "%s(%s)\n", $sub->{name}, join ", ", map { $_->{name} } @{ $sub->{params} };
+//! }
+//! ```
+
+use std::fmt::Write;
+
+use crate::context::Context;
+use crate::SV;
+
+/// Description of a single subroutine parameter.
+#[derive(Debug, Clone, Copy)]
+pub struct Param {
+    /// Parameter name as written in the `xs!` block.
+    pub name: &'static str,
+    /// Rust type of the parameter as written in the `xs!` block.
+    pub ty: &'static str,
+}
+
+/// Description of a subroutine defined with the `xs!` macro.
+#[derive(Debug, Clone, Copy)]
+pub struct Signature {
+    /// Perl package the subroutine belongs to.
+    pub package: &'static str,
+    /// Subroutine name, without the package.
+    pub name: &'static str,
+    /// Parameters in the order they are taken from the Perl stack.
+    pub params: &'static [Param],
+    /// Lines of the doc comment attached to the subroutine.
+    pub doc: &'static [&'static str],
+}
+
+impl Signature {
+    /// Return fully qualified subroutine name.
+    pub fn full_name(&self) -> String {
+        format!("{}::{}", self.package, self.name)
+    }
+
+    /// Return doc comment as a single string.
+    ///
+    /// Leading space that `///` comments usually have is removed from each line.
+    pub fn doc(&self) -> String {
+        let lines: Vec<&str> = self.doc
+            .iter()
+            .map(|line| if line.starts_with(' ') { &line[1..] } else { line })
+            .collect();
+        lines.join("\n")
+    }
+}
+
+/// Convert signature table into a Perl data structure.
+///
+/// Returns a reference to an array of hashes, one per subroutine, with keys `package`, `name`,
+/// `doc` and `params`. `params` is an array of hashes with keys `name` and `type`.
+pub fn signatures_to_sv(ctx: &mut Context, sigs: &[Signature]) -> SV {
+    let subs = ctx.new_av();
+    for sig in sigs {
+        let params = ctx.new_av();
+        for param in sig.params {
+            let hv = ctx.new_hv();
+            hv.store("name", ctx.new_sv(param.name));
+            hv.store("type", ctx.new_sv(param.ty));
+            params.push(ctx.new_sv(hv));
+        }
+
+        let hv = ctx.new_hv();
+        hv.store("package", ctx.new_sv(sig.package));
+        hv.store("name", ctx.new_sv(sig.name));
+        hv.store("doc", ctx.new_sv(sig.doc()));
+        hv.store("params", ctx.new_sv(params));
+        subs.push(ctx.new_sv(hv));
+    }
+    ctx.new_sv(subs)
+}
+
+/// Render a `.pm` skeleton with POD documentation for the subroutines.
+///
+/// Generated module loads the XS part with `XSLoader` and documents each subroutine with its
+/// parameters, Rust types and doc comment.
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// mod acme {
+///     xs! {
+///         package Acme;
+///         /// Add two numbers.
+///         sub add(ctx, a: perl_xs::IV, b: perl_xs::IV) { a + b }
+///     }
+/// }
+/// # fn main() {
+/// let pm = perl_xs::meta::render_pm("Acme", acme::PERL_XS_SIGNATURES);
+/// assert!(pm.contains("=head2 add($a, $b)"));
+/// # }
+/// ```
+pub fn render_pm(package: &str, sigs: &[Signature]) -> String {
+    let mut pm = String::new();
+
+    let _ = writeln!(pm, "package {};", package);
+    let _ = writeln!(pm);
+    let _ = writeln!(pm, "use strict;");
+    let _ = writeln!(pm, "use warnings;");
+    let _ = writeln!(pm);
+    let _ = writeln!(pm, "require XSLoader;");
+    let _ = writeln!(pm, "XSLoader::load();");
+    let _ = writeln!(pm);
+    let _ = writeln!(pm, "1;");
+    let _ = writeln!(pm, "__END__");
+    let _ = writeln!(pm);
+    let _ = writeln!(pm, "=head1 NAME");
+    let _ = writeln!(pm);
+    let _ = writeln!(pm, "{}", package);
+    let _ = writeln!(pm);
+    let _ = writeln!(pm, "=head1 FUNCTIONS");
+
+    for sig in sigs {
+        let names: Vec<String> = sig.params.iter().map(|p| format!("${}", p.name)).collect();
+        let _ = writeln!(pm);
+        let _ = writeln!(pm, "=head2 {}({})", sig.name, names.join(", "));
+
+        let doc = sig.doc();
+        if !doc.is_empty() {
+            let _ = writeln!(pm);
+            let _ = writeln!(pm, "{}", doc);
+        }
+
+        if !sig.params.is_empty() {
+            let _ = writeln!(pm);
+            let _ = writeln!(pm, "=over");
+            for param in sig.params {
+                let _ = writeln!(pm);
+                let _ = writeln!(pm, "=item C<${}> (C<{}>)", param.name, param.ty);
+            }
+            let _ = writeln!(pm);
+            let _ = writeln!(pm, "=back");
+        }
+    }
+
+    let _ = writeln!(pm);
+    let _ = writeln!(pm, "=cut");
+
+    pm
+}
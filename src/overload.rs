@@ -0,0 +1,59 @@
+//! Operator overloading for Rust-backed classes.
+//!
+//! The [`overload!`](../macro.overload.html) macro installs operator implementations the same way
+//! [`overload`](http://perldoc.perl.org/overload.html) does: each operator becomes a subroutine
+//! named `(` followed by the operator, and the `fallback` value is stored in the `()` glob of the
+//! package.
+//!
+//! Operator subroutines receive the object, the other operand (`undef` for unary operators) and a
+//! flag that is true when the operands were swapped:
+//!
+//! ```
+//! # #[macro_use] extern crate perl_xs;
+//! # #[macro_use] extern crate perl_sys;
+//! mod decimal {
+//!     use perl_xs::{DataRef, IV, SV};
+//!
+//!     pub struct Decimal(IV);
+//!
+//!     overload! {
+//!         package Acme::Decimal;
+//!         fallback = true;
+//!         "\"\"" => sub to_string(ctx, this: DataRef<Decimal>) {
+//!             format!("{}.{:02}", this.0 / 100, this.0 % 100)
+//!         }
+//!         "<=>" => sub compare(ctx, this: DataRef<Decimal>, other: DataRef<Decimal>, swapped: bool) {
+//!             let ord = this.0.cmp(&other.0) as IV;
+//!             if swapped { -ord } else { ord }
+//!         }
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Like `xs!` packages, the module needs to be added to the bootstrap block, and also listed with
+//! `boot` to set the fallback mode:
+//!
+//! ```
+//! # #[macro_use] extern crate perl_xs;
+//! # #[macro_use] extern crate perl_sys;
+//! # mod decimal { overload! { package Acme::Decimal; fallback = true; } }
+//! xs! {
+//!     bootstrap boot_Acme;
+//!     use decimal;
+//!     boot decimal;
+//! }
+//! # fn main() {}
+//! ```
+
+use std::ffi::CString;
+
+use crate::context::Context;
+use crate::raw;
+
+#[doc(hidden)]
+pub fn set_fallback(ctx: &mut Context, package: &str, fallback: bool) {
+    let name = CString::new(format!("{}::()", package)).unwrap();
+    let sv = ctx.get_sv_add(&name);
+    unsafe { sv.pthx().sv_setiv(sv.as_ptr(), fallback as raw::IV) };
+}
@@ -0,0 +1,107 @@
+use std::borrow::Cow;
+use std::ops::Deref;
+use std::slice;
+use std::str;
+
+use crate::raw;
+use crate::scalar::decode;
+use crate::SV;
+
+/// String buffer of an SV, pinned for safe borrowing.
+///
+/// The guard holds its own copy of the SV, which no other code can reach, so the buffer can't be
+/// modified, reallocated or freed while the guard is alive, whatever Perl code runs in between.
+/// Changes made to the original SV after pinning are not visible through the guard.
+///
+/// Plain strings share the buffer with the original SV using Perl's copy-on-write, so large
+/// strings are usually not copied. Magical scalars and references are stringified into the copy,
+/// since their string value is not stored in their own buffer.
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use std::ffi::CString;
+/// # use perl_xs::{IV, SV};
+/// # xs! {
+/// #   package Dummy;
+/// sub count_lines(ctx, text: SV) {
+///     let text = text.pin();
+///     // Calling into Perl is fine, the buffer can't change.
+///     ctx.call_pv(&CString::new("Dummy::progress").unwrap(), perl_xs::G_DISCARD);
+///     text.iter().filter(|&&b| b == b'\n').count() as IV
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+pub struct PinnedStr {
+    _sv: SV,
+    ptr: *const u8,
+    len: usize,
+    utf8: bool,
+}
+
+impl PinnedStr {
+    pub(crate) fn new(sv: &SV) -> PinnedStr {
+        let pthx = sv.pthx();
+        let sv = unsafe {
+            let raw = sv.as_ptr();
+            if (*raw).sv_flags & raw::SVs_GMG != 0 || pthx.ouroboros_sv_rok(raw) != 0 {
+                let mut len = 0;
+                let ptr = pthx.ouroboros_sv_pv(raw, &mut len);
+                let flags = if pthx.ouroboros_sv_utf8(raw) != 0 { raw::SVf_UTF8 } else { 0 };
+                SV::from_raw_owned(pthx, pthx.newSVpvn_flags(ptr, len, flags as raw::U32))
+            } else {
+                let copy = pthx.newSV(0);
+                pthx.sv_setsv_flags(copy, raw, raw::SV_NOSTEAL as _);
+                SV::from_raw_owned(pthx, copy)
+            }
+        };
+
+        let raw = sv.as_ptr();
+        unsafe {
+            let mut len = 0;
+            let ptr = pthx.sv_2pv_flags(raw, &mut len, 0);
+            let utf8 = pthx.ouroboros_sv_utf8(raw) != 0;
+            PinnedStr {
+                _sv: sv,
+                ptr: ptr as *const u8,
+                len: len as usize,
+                utf8: utf8,
+            }
+        }
+    }
+
+    /// Return true if the string is a character string with the UTF8 flag.
+    #[inline]
+    pub fn utf8(&self) -> bool {
+        self.utf8
+    }
+
+    /// Get characters of the string.
+    ///
+    /// See [`SV::as_str`](struct.SV.html#method.as_str).
+    #[inline]
+    pub fn as_str(&self) -> Result<Cow<str>, str::Utf8Error> {
+        decode(&self[..], self.utf8)
+    }
+}
+
+impl Deref for PinnedStr {
+    type Target = [u8];
+
+    #[inline]
+    fn deref(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.ptr, self.len) }
+    }
+}
+
+impl SV {
+    /// Pin the string buffer of the SV and borrow it safely.
+    ///
+    /// This is a safe alternative to [`as_slice`](#method.as_slice) that usually does not copy
+    /// plain strings. See [`PinnedStr`](struct.PinnedStr.html).
+    #[inline]
+    pub fn pin(&self) -> PinnedStr {
+        PinnedStr::new(self)
+    }
+}
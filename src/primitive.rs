@@ -0,0 +1,266 @@
+//! Conversions for Rust primitive types that do not match Perl types exactly.
+//!
+//! Integers narrower than `IV` are range-checked instead of being truncated, integers wider than
+//! `IV` fall back to their decimal string form.
+
+use std::convert::TryFrom;
+use std::time::Duration;
+use std::{f32, slice};
+
+use crate::convert::{get_magic, BigInt, IntoSV, TryFromSV};
+use crate::error::{CharError, NumericError, RangeError};
+use crate::raw;
+use crate::raw::{IV, NV, UV};
+use crate::scalar::decode;
+use crate::SV;
+
+/// Integer value of a scalar, which may not fit in an `IV`.
+pub(crate) enum Integer {
+    Signed(IV),
+    Unsigned(UV),
+}
+
+pub(crate) unsafe fn integer_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Integer {
+    let iv = pthx.sv_2iv_flags(raw, 0);
+    if pthx.ouroboros_sv_rok(raw) == 0 && (*raw).sv_flags & raw::SVf_IVisUV != 0 {
+        Integer::Unsigned(iv as UV)
+    } else {
+        Integer::Signed(iv)
+    }
+}
+
+fn out_of_range<T: ToString>(value: T, ty: &'static str) -> RangeError {
+    RangeError {
+        value: value.to_string(),
+        ty: ty,
+    }
+}
+
+macro_rules! integer {
+    ($($ty:ident, $wide:ident;)*) => ($(
+        /// Integer value of the scalar. Values outside of the range of the type are an error.
+        impl TryFromSV for $ty {
+            type Error = RangeError;
+
+            #[inline]
+            unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                get_magic(pthx, raw);
+                Self::try_from_sv_nomg(pthx, raw)
+            }
+
+            #[inline]
+            unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                match integer_nomg(pthx, raw) {
+                    Integer::Signed(v) => $ty::try_from(v).map_err(|_| out_of_range(v, stringify!($ty))),
+                    Integer::Unsigned(v) => $ty::try_from(v).map_err(|_| out_of_range(v, stringify!($ty))),
+                }
+            }
+        }
+
+        impl IntoSV for $ty {
+            #[inline]
+            fn into_sv(self, pthx: raw::Interpreter) -> SV {
+                (self as $wide).into_sv(pthx)
+            }
+        }
+    )*)
+}
+
+integer! {
+    i8, IV;
+    i16, IV;
+    i32, IV;
+    isize, IV;
+    u8, UV;
+    u16, UV;
+    u32, UV;
+    usize, UV;
+}
+
+/// Floating point value of the scalar. Finite values outside of the range of `f32` are an error.
+impl TryFromSV for f32 {
+    type Error = RangeError;
+
+    #[inline]
+    unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, raw);
+        Self::try_from_sv_nomg(pthx, raw)
+    }
+
+    #[inline]
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        let nv = pthx.sv_2nv_flags(raw, 0);
+        if nv.is_finite() && nv.abs() > f32::MAX as NV {
+            Err(out_of_range(nv, "f32"))
+        } else {
+            Ok(nv as f32)
+        }
+    }
+}
+
+impl IntoSV for f32 {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        (self as NV).into_sv(pthx)
+    }
+}
+
+/// The only character of a string. Strings of any other length are an error.
+impl TryFromSV for char {
+    type Error = CharError;
+
+    #[inline]
+    unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, raw);
+        Self::try_from_sv_nomg(pthx, raw)
+    }
+
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        let mut len = 0;
+        let ptr = pthx.sv_2pv_flags(raw, &mut len, 0);
+        let bytes = slice::from_raw_parts(ptr as *const u8, len as usize);
+        let s = decode(bytes, pthx.ouroboros_sv_utf8(raw) != 0).map_err(CharError::Utf8)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(CharError::Length(s.chars().count())),
+        }
+    }
+}
+
+impl IntoSV for char {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        let mut buf = [0; 4];
+        (&*self.encode_utf8(&mut buf)).into_sv(pthx)
+    }
+}
+
+/// Duration in seconds, possibly fractional. Negative and non-finite values are an error.
+impl TryFromSV for Duration {
+    type Error = RangeError;
+
+    #[inline]
+    unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, raw);
+        Self::try_from_sv_nomg(pthx, raw)
+    }
+
+    #[inline]
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        let nv = pthx.sv_2nv_flags(raw, 0);
+        if nv >= 0.0 && nv < u64::max_value() as NV {
+            Ok(Duration::from_secs_f64(nv as f64))
+        } else {
+            Err(out_of_range(nv, "Duration"))
+        }
+    }
+}
+
+/// Create a number of seconds, possibly fractional.
+impl IntoSV for Duration {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        (self.as_secs_f64() as NV).into_sv(pthx)
+    }
+}
+
+unsafe fn string_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> String {
+    let mut len = 0;
+    let ptr = pthx.sv_2pv_flags(raw, &mut len, 0);
+    String::from_utf8_lossy(slice::from_raw_parts(ptr as *const u8, len as usize)).into_owned()
+}
+
+fn is_decimal(s: &str) -> bool {
+    let digits = s.trim_start_matches(|c| c == '+' || c == '-');
+    s.len() - digits.len() <= 1 && !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit())
+}
+
+fn looks_like_float(s: &str) -> bool {
+    s.parse::<f64>().is_ok() && !s.eq_ignore_ascii_case("nan") && !s.eq_ignore_ascii_case("inf")
+}
+
+macro_rules! wide_integer {
+    ($($ty:ident;)*) => ($(
+        /// Integer value of the scalar.
+        ///
+        /// Integers too large for `IV` and `UV` are parsed from their decimal string form, which
+        /// also covers `Math::BigInt` and `Math::BigFloat` objects. Undefined values and
+        /// non-integers are an error.
+        impl TryFromSV for $ty {
+            type Error = NumericError;
+
+            #[inline]
+            unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                get_magic(pthx, raw);
+                Self::try_from_sv_nomg(pthx, raw)
+            }
+
+            unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                if pthx.ouroboros_sv_ok(raw) == 0 {
+                    return Err(NumericError::Undef);
+                }
+                let flags = (*raw).sv_flags;
+                let rok = pthx.ouroboros_sv_rok(raw) != 0;
+                if !rok && flags & raw::SVf_IOK != 0 {
+                    let range = |v: String| NumericError::Range(out_of_range(v, stringify!($ty)));
+                    return match integer_nomg(pthx, raw) {
+                        Integer::Signed(v) => $ty::try_from(v).map_err(|_| range(v.to_string())),
+                        Integer::Unsigned(v) => $ty::try_from(v).map_err(|_| range(v.to_string())),
+                    };
+                }
+                if !rok && flags & raw::SVf_POK == 0 && flags & raw::SVf_NOK != 0 {
+                    let nv = pthx.sv_2nv_flags(raw, 0);
+                    if nv.is_finite() && nv.trunc() == nv {
+                        return if nv >= $ty::min_value() as NV && nv < $ty::max_value() as NV {
+                            Ok(nv as $ty)
+                        } else {
+                            Err(NumericError::Range(out_of_range(nv, stringify!($ty))))
+                        };
+                    }
+                }
+                let s = string_nomg(pthx, raw);
+                let t = s.trim();
+                if is_decimal(t) {
+                    t.parse().map_err(|_| NumericError::Range(out_of_range(t, stringify!($ty))))
+                } else if looks_like_float(t) {
+                    Err(NumericError::NotInteger(s))
+                } else {
+                    Err(NumericError::NotNumber(s))
+                }
+            }
+        }
+
+        /// Create a number if the value fits in a native integer, a decimal string otherwise.
+        impl IntoSV for $ty {
+            fn into_sv(self, pthx: raw::Interpreter) -> SV {
+                if let Ok(v) = IV::try_from(self) {
+                    v.into_sv(pthx)
+                } else if let Ok(v) = UV::try_from(self) {
+                    v.into_sv(pthx)
+                } else {
+                    (&self.to_string()[..]).into_sv(pthx)
+                }
+            }
+        }
+
+        impl TryFromSV for BigInt<$ty> {
+            type Error = NumericError;
+
+            #[inline]
+            unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                $ty::try_from_sv(pthx, raw).map(BigInt)
+            }
+
+            #[inline]
+            unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                $ty::try_from_sv_nomg(pthx, raw).map(BigInt)
+            }
+        }
+    )*)
+}
+
+wide_integer! {
+    i128;
+    u128;
+}
@@ -12,6 +12,14 @@ pub use perl_sys::types::*;
 
 pub type Interpreter = perl_sys::Perl;
 pub type Stack = OuroborosStack;
+pub type MultiCall = OuroborosMultiCall;
+
+/// Perl exception value carried by a panic, rethrown with `croak_sv` as is.
+///
+/// Holds a reference to the value, which is passed to the interpreter when rethrown.
+pub(crate) struct Exception(pub *mut SV);
+
+unsafe impl Send for Exception {}
 
 pub unsafe fn catch_unwind<F, T>(perl: Interpreter, f: F) -> T
 where
@@ -37,6 +45,10 @@ unsafe fn rethrow_panic(perl: Interpreter, e: Box<dyn any::Any>) -> ! {
         errsv = make_error_sv(perl, msg);
     }
 
+    if let Some(&Exception(sv)) = e.downcast_ref::<Exception>() {
+        errsv = perl.sv_2mortal(sv);
+    }
+
     mem::drop(e);
 
     if errsv.is_null() {
@@ -1,15 +1,17 @@
 use std;
-use std::{mem, ptr, slice, string};
-use std::any::Any;
-use std::ops::Deref;
-use std::os::raw::{c_char, c_int};
+use std::borrow::Cow;
+use std::ffi::CStr;
+use std::rc::Rc;
+use std::sync::Arc;
+use std::{mem, slice, str, string};
 
 use crate::raw;
 use crate::raw::{IV, NV, UV};
 use crate::raw::{SVt_PVAV, SVt_PVCV, SVt_PVGV, SVt_PVHV};
 
 use crate::array::AV;
-use crate::convert::{FromSV, IntoSV, TryFromSV};
+use crate::convert::{get_magic, FromSV, IntoSV, TryFromSV};
+use crate::error::ReadOnlyError;
 use crate::handle::Owned;
 use crate::hash::HV;
 
@@ -43,6 +45,12 @@ impl SV {
         /// [`SvOK`](http://perldoc.perl.org/perlapi.html#SvOK).
         simple fn ok() -> bool = ouroboros_sv_ok() != 0
     }
+    method! {
+        /// Return true if SV is true in boolean context.
+        ///
+        /// Perl macro: [`SvTRUE`](http://perldoc.perl.org/perlapi.html#SvTRUE).
+        simple fn is_true() -> bool = sv_2bool_flags(raw::SV_GMAGIC as _) != 0
+    }
     method! {
         /// Return true if SV contains a signed integer.
         ///
@@ -151,12 +159,145 @@ impl SV {
 
     /// Return a copy of string in the SV.
     ///
+    /// This method interprets the internal buffer as UTF-8 regardless of the UTF8 flag. Use
+    /// `String` conversion or [`as_str`](#method.as_str) to get the characters of the string.
+    ///
     /// Perl macro: [`SvPV`](http://perldoc.perl.org/perlapi.html#SvPV).
     #[inline]
     pub fn to_string(&self) -> Result<String, string::FromUtf8Error> {
         String::from_utf8(self.to_vec())
     }
 
+    /// Get characters of the string in the SV.
+    ///
+    /// Strings with the UTF8 flag and ASCII strings are borrowed from the internal buffer, other
+    /// byte strings are decoded as Latin-1 into a new `String`. Error is returned only if the UTF8
+    /// flag is set on a malformed buffer.
+    ///
+    /// This method is unsafe for the same reasons as [`as_slice`](#method.as_slice).
+    #[inline]
+    pub unsafe fn as_str(&self) -> Result<Cow<str>, str::Utf8Error> {
+        let bytes = self.as_slice();
+        decode(bytes, self.utf8())
+    }
+
+    /// Convert the string in the SV into UTF-8 encoded form and set the UTF8 flag.
+    ///
+    /// Characters of the string do not change.
+    ///
+    /// Perl function: [`sv_utf8_upgrade`](http://perldoc.perl.org/perlapi.html#sv_utf8_upgrade).
+    #[inline]
+    pub fn utf8_upgrade(&self) {
+        unsafe { self.pthx().sv_utf8_upgrade_flags_grow(self.as_ptr(), raw::SV_GMAGIC as _, 0) };
+    }
+
+    /// Convert the string in the SV from UTF-8 encoded form into bytes and clear the UTF8 flag.
+    ///
+    /// Characters of the string do not change. Return false and leave the SV unchanged if the
+    /// string contains characters above 255.
+    ///
+    /// Perl function: [`sv_utf8_downgrade`](http://perldoc.perl.org/perlapi.html#sv_utf8_downgrade).
+    #[inline]
+    pub fn utf8_downgrade(&self) -> bool {
+        unsafe { self.pthx().sv_utf8_downgrade(self.as_ptr(), 1) != 0 }
+    }
+
+    /// Encode characters of the string as UTF-8 bytes and clear the UTF8 flag.
+    ///
+    /// Perl function: [`sv_utf8_encode`](http://perldoc.perl.org/perlapi.html#sv_utf8_encode).
+    #[inline]
+    pub fn utf8_encode(&self) {
+        unsafe { self.pthx().sv_utf8_encode(self.as_ptr()) };
+    }
+
+    /// Decode UTF-8 bytes of the string into characters, setting the UTF8 flag if needed.
+    ///
+    /// Return false if the string is not valid UTF-8.
+    ///
+    /// Perl function: [`sv_utf8_decode`](http://perldoc.perl.org/perlapi.html#sv_utf8_decode).
+    #[inline]
+    pub fn utf8_decode(&self) -> bool {
+        unsafe { self.pthx().sv_utf8_decode(self.as_ptr()) != 0 }
+    }
+
+    /// Return true if SV can not be modified.
+    ///
+    /// Both read-only and protected SVs, like `undef` or constants, are reported.
+    ///
+    /// Perl macro: [`SvREADONLY`](http://perldoc.perl.org/perlapi.html#SvREADONLY).
+    #[inline]
+    pub fn is_readonly(&self) -> bool {
+        unsafe { (*self.as_ptr()).sv_flags & (raw::SVf_READONLY | raw::SVf_PROTECT) != 0 }
+    }
+
+    /// Die with a Perl exception if the SV is read-only, before Perl itself would croak in the
+    /// middle of Rust code.
+    #[inline]
+    fn check_writable(&self) {
+        if self.is_readonly() {
+            croak!("{}", ReadOnlyError);
+        }
+    }
+
+    /// Set SV to an integer and process its set-magic.
+    ///
+    /// Setters die with "modification of a read-only value attempted" if the SV is read-only.
+    ///
+    /// Perl function: [`sv_setiv_mg`](http://perldoc.perl.org/perlapi.html#sv_setiv_mg).
+    #[inline]
+    pub fn set_iv(&self, v: IV) {
+        self.check_writable();
+        unsafe { self.pthx().sv_setiv_mg(self.as_ptr(), v) };
+    }
+
+    /// Set SV to an unsigned integer and process its set-magic.
+    ///
+    /// Perl function: [`sv_setuv_mg`](http://perldoc.perl.org/perlapi.html#sv_setuv_mg).
+    #[inline]
+    pub fn set_uv(&self, v: UV) {
+        self.check_writable();
+        unsafe { self.pthx().sv_setuv_mg(self.as_ptr(), v) };
+    }
+
+    /// Set SV to a floating point value and process its set-magic.
+    ///
+    /// Perl function: [`sv_setnv_mg`](http://perldoc.perl.org/perlapi.html#sv_setnv_mg).
+    #[inline]
+    pub fn set_nv(&self, v: NV) {
+        self.check_writable();
+        unsafe { self.pthx().sv_setnv_mg(self.as_ptr(), v) };
+    }
+
+    /// Copy value of another SV into this one and process set-magic.
+    ///
+    /// Perl function: [`sv_setsv_mg`](http://perldoc.perl.org/perlapi.html#sv_setsv_mg).
+    #[inline]
+    pub fn set_sv(&self, v: &SV) {
+        self.check_writable();
+        unsafe { self.pthx().sv_setsv_mg(self.as_ptr(), v.as_ptr()) };
+    }
+
+    /// Set SV to a character string and process its set-magic.
+    ///
+    /// The UTF8 flag is set only if the string is not ASCII, same as for `&str` conversion.
+    #[inline]
+    pub fn set_str(&self, v: &str) {
+        self.set_sv(&v.into_sv(self.pthx()));
+    }
+
+    /// Set SV to `undef` and process its set-magic.
+    #[inline]
+    pub fn set_undef(&self) {
+        self.check_writable();
+        unsafe { self.pthx().sv_setsv_mg(self.as_ptr(), self.pthx().ouroboros_sv_undef()) };
+    }
+
+    /// Set SV to a value of any convertible type and process its set-magic.
+    #[inline]
+    pub fn set<T: IntoSV>(&self, v: T) {
+        self.set_sv(&v.into_sv(self.pthx()));
+    }
+
     method! {
         /// Return true if SV contains a Perl reference.
         ///
@@ -197,6 +338,75 @@ impl SV {
         self.deref().and_then(|sv| sv.into_hv())
     }
 
+    /// Weaken the reference.
+    ///
+    /// Weak reference does not keep the referenced value alive, and becomes `undef` when the value
+    /// is freed. Does nothing if `self` is not a reference or is already weak.
+    ///
+    /// Perl function: [`sv_rvweaken`](http://perldoc.perl.org/perlapi.html#sv_rvweaken).
+    #[inline]
+    pub fn weaken(&self) {
+        if self.rv_ok() && !self.is_weak() {
+            unsafe { self.pthx().sv_rvweaken(self.as_ptr()) };
+        }
+    }
+
+    /// Return true if SV is a weak reference.
+    ///
+    /// Perl macro: `SvWEAKREF`.
+    #[inline]
+    pub fn is_weak(&self) -> bool {
+        self.rv_ok() && unsafe { (*self.as_ptr()).sv_flags & raw::SVprv_WEAKREF != 0 }
+    }
+
+    /// Return address of the referenced value.
+    ///
+    /// Return `None` if `self` is not a reference.
+    ///
+    /// See: [`Scalar::Util::refaddr`](http://perldoc.perl.org/Scalar/Util.html#refaddr)
+    #[inline]
+    pub fn refaddr(&self) -> Option<usize> {
+        if self.rv_ok() {
+            Some(unsafe { self.deref_raw() } as usize)
+        } else {
+            None
+        }
+    }
+
+    /// Return basic type of the referenced value (`SCALAR`, `ARRAY`, `HASH`, `CODE`, etc),
+    /// ignoring any blessing.
+    ///
+    /// Return `None` if `self` is not a reference.
+    ///
+    /// See: [`Scalar::Util::reftype`](http://perldoc.perl.org/Scalar/Util.html#reftype)
+    #[inline]
+    pub fn reftype(&self) -> Option<String> {
+        if self.rv_ok() {
+            Some(unsafe { self.reftype_raw(false) })
+        } else {
+            None
+        }
+    }
+
+    /// Return name of the package the referenced object is blessed into.
+    ///
+    /// Return `None` if `self` is not a reference to a blessed object.
+    ///
+    /// See: [`Scalar::Util::blessed`](http://perldoc.perl.org/Scalar/Util.html#blessed)
+    #[inline]
+    pub fn blessed(&self) -> Option<String> {
+        if unsafe { self.pthx().sv_isobject(self.as_ptr()) } != 0 {
+            Some(unsafe { self.reftype_raw(true) })
+        } else {
+            None
+        }
+    }
+
+    unsafe fn reftype_raw(&self, ob: bool) -> String {
+        let name = self.pthx().sv_reftype(self.deref_raw(), ob as _);
+        CStr::from_ptr(name).to_string_lossy().into_owned()
+    }
+
     /// Cast SV into AV.
     #[inline]
     pub fn into_av(self) -> Option<AV> {
@@ -247,55 +457,25 @@ impl SV {
         raw
     }
 
-    /// Create a new reference to this scalar, transferring ownership to the reference.
-    #[inline]
-    pub fn into_ref(self) -> SV {
-        let pthx = self.pthx();
-        unsafe {
-            let rv = pthx.newRV_noinc(self.into_raw());
-            SV::from_raw_owned(pthx, rv)
-        }
-    }
-
-    /// Store a Rust value inside the SV.
+    /// Hand the scalar over to Perl as a temporary value and return the raw pointer.
     ///
-    /// SV takes ownership of the `value`, which will be dropped when the SV reference count drops
-    /// to zero. It is possible to attach multiple values to the SV, but currently only one can be
-    /// retrieved.
+    /// The scalar stays alive until the temporaries of the current scope are freed, at the end
+    /// of the statement that called the XSUB or at [`Scope::free_tmps`](context/struct.Scope.html#method.free_tmps).
     ///
-    /// This relies on [Perl magic](http://perldoc.perl.org/perlguts.html#Magic-Virtual-Tables) to
-    /// store the value. Magic is not copied on scalar assignment, so to be useful, scalars with
-    /// magic need to be passed around by reference.
-    pub fn add_data(&self, value: Box<dyn Any>) {
+    /// Perl function: [`sv_2mortal`](http://perldoc.perl.org/perlapi.html#sv_2mortal).
+    #[inline]
+    pub fn into_mortal(self) -> *mut raw::SV {
         let pthx = self.pthx();
-        let svp = self.as_ptr();
-        let obj = ptr::null_mut();
-        let ptr = Box::into_raw(Box::new(value)) as *const c_char;
-        let len = 0;
-        unsafe {
-            pthx.sv_magicext(svp, obj, raw::PERL_MAGIC_ext as _, &VTBL_ANY, ptr, len);
-        }
+        unsafe { pthx.sv_2mortal(self.into_raw()) }
     }
 
-    /// Get a reference to a Rust value stored in the SV.
-    ///
-    /// `Ref` takes ownership of the SV to protect the value from being dropped while reference is
-    /// alive.
-    ///
-    /// `None` is returned if no value was found in the SV.
-    pub fn into_data_ref(self) -> Option<DataRef<dyn Any>> {
+    /// Create a new reference to this scalar, transferring ownership to the reference.
+    #[inline]
+    pub fn into_ref(self) -> SV {
         let pthx = self.pthx();
         unsafe {
-            let magic = pthx.mg_findext(self.as_ptr(), raw::PERL_MAGIC_ext as _, &VTBL_ANY);
-            if !magic.is_null() {
-                let ptr = (*magic).mg_ptr as *const Box<dyn Any>;
-                Some(DataRef {
-                    owner: self,
-                    inner: (*ptr).as_ref(),
-                })
-            } else {
-                None
-            }
+            let rv = pthx.newRV_noinc(self.into_raw());
+            SV::from_raw_owned(pthx, rv)
         }
     }
 
@@ -319,12 +499,12 @@ impl SV {
     }
 
     #[inline]
-    fn pthx(&self) -> raw::Interpreter {
+    pub(crate) fn pthx(&self) -> raw::Interpreter {
         self.0.pthx()
     }
 
     #[inline]
-    fn as_ptr(&self) -> *mut raw::SV {
+    pub(crate) fn as_ptr(&self) -> *mut raw::SV {
         self.0.as_ptr()
     }
 }
@@ -335,68 +515,159 @@ impl Clone for SV {
     }
 }
 
-impl FromSV for IV {
+macro_rules! from_sv_numeric {
+    ($($ty:ty, $conv:ident;)*) => ($(
+        impl FromSV for $ty {
+            #[inline]
+            unsafe fn from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> $ty {
+                get_magic(pthx, raw);
+                Self::from_sv_nomg(pthx, raw)
+            }
+
+            #[inline]
+            unsafe fn from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> $ty {
+                pthx.$conv(raw, 0)
+            }
+        }
+    )*)
+}
+
+from_sv_numeric! {
+    IV, sv_2iv_flags;
+    UV, sv_2uv_flags;
+    NV, sv_2nv_flags;
+}
+
+impl FromSV for SV {
     #[inline]
-    unsafe fn from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> IV {
-        pthx.sv_iv(raw)
+    unsafe fn from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> SV {
+        SV::from_raw_borrowed(pthx, raw)
     }
 }
 
-impl FromSV for UV {
-    #[inline]
-    unsafe fn from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> UV {
-        pthx.sv_uv(raw)
+/// Decode string buffer of an SV into characters.
+pub(crate) fn decode(bytes: &[u8], utf8: bool) -> Result<Cow<str>, str::Utf8Error> {
+    if utf8 || bytes.is_ascii() {
+        str::from_utf8(bytes).map(Cow::Borrowed)
+    } else {
+        Ok(Cow::Owned(bytes.iter().map(|&b| b as char).collect()))
     }
 }
 
-impl FromSV for NV {
+/// Characters of the string.
+///
+/// Strings without the UTF8 flag are decoded as Latin-1, as Perl does.
+impl TryFromSV for String {
+    type Error = str::Utf8Error;
+
     #[inline]
-    unsafe fn from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> NV {
-        pthx.sv_nv(raw)
+    unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, raw);
+        Self::try_from_sv_nomg(pthx, raw)
     }
-}
 
-impl FromSV for SV {
     #[inline]
-    unsafe fn from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> SV {
-        SV::from_raw_borrowed(pthx, raw)
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        Cow::<str>::try_from_sv_nomg(pthx, raw).map(Cow::into_owned)
     }
 }
 
-macro_rules! from_sv_for_option {
-    ($($fn:ident, $ty:ty;)*) => ($(
-        /// Return `Some(v)` if scalar value is defined, `None` otherwise.
-        impl FromSV for Option<$ty> {
-            #[inline]
-            unsafe fn from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Option<$ty> {
-                if pthx.ouroboros_sv_ok(raw) != 0 {
-                    Some(pthx.$fn(raw))
-                } else {
-                    None
-                }
-            }
-        }
-    )*)
-}
+impl TryFromSV for Box<str> {
+    type Error = str::Utf8Error;
+
+    #[inline]
+    unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        String::try_from_sv(pthx, raw).map(String::into_boxed_str)
+    }
 
-from_sv_for_option! {
-    sv_iv, IV;
-    sv_uv, UV;
-    sv_nv, NV;
+    #[inline]
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        String::try_from_sv_nomg(pthx, raw).map(String::into_boxed_str)
+    }
 }
 
-impl TryFromSV for String {
-    type Error = std::str::Utf8Error;
+/// Characters of the string.
+///
+/// Strings with the UTF8 flag and ASCII-only strings are borrowed from the scalar's buffer, other
+/// strings are decoded as Latin-1 into an owned value. A borrowed value is valid only as long as
+/// the scalar is alive and not modified, which holds for subroutine arguments.
+impl<'a> TryFromSV for Cow<'a, str> {
+    type Error = str::Utf8Error;
 
     #[inline]
     unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+        get_magic(pthx, raw);
+        Self::try_from_sv_nomg(pthx, raw)
+    }
+
+    #[inline]
+    unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
         let mut len = 0;
-        let ptr = pthx.ouroboros_sv_pv(raw, &mut len);
+        let ptr = pthx.sv_2pv_flags(raw, &mut len, 0);
         let bytes = slice::from_raw_parts(ptr as *const u8, len as usize);
-        Ok(std::str::from_utf8(bytes)?.to_owned())
+        let utf8 = pthx.ouroboros_sv_utf8(raw) != 0;
+        decode(bytes, utf8)
     }
 }
 
+macro_rules! shared {
+    ($($ptr:ident;)*) => ($(
+        impl<T> TryFromSV for $ptr<T>
+        where
+            T: TryFromSV,
+        {
+            type Error = T::Error;
+
+            #[inline]
+            unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                T::try_from_sv(pthx, raw).map($ptr::new)
+            }
+
+            #[inline]
+            unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                T::try_from_sv_nomg(pthx, raw).map($ptr::new)
+            }
+        }
+
+        impl TryFromSV for $ptr<str> {
+            type Error = str::Utf8Error;
+
+            #[inline]
+            unsafe fn try_from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                String::try_from_sv(pthx, raw).map($ptr::from)
+            }
+
+            #[inline]
+            unsafe fn try_from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> Result<Self, Self::Error> {
+                String::try_from_sv_nomg(pthx, raw).map($ptr::from)
+            }
+        }
+
+        /// Convert the shared value, cloning it if there are other references to it.
+        impl<T> IntoSV for $ptr<T>
+        where
+            T: IntoSV + Clone,
+        {
+            #[inline]
+            fn into_sv(self, pthx: raw::Interpreter) -> SV {
+                $ptr::try_unwrap(self).unwrap_or_else(|ptr| (*ptr).clone()).into_sv(pthx)
+            }
+        }
+
+        impl IntoSV for $ptr<str> {
+            #[inline]
+            fn into_sv(self, pthx: raw::Interpreter) -> SV {
+                (&*self).into_sv(pthx)
+            }
+        }
+    )*)
+}
+
+shared! {
+    Rc;
+    Arc;
+}
+
 impl IntoSV for IV {
     #[inline]
     fn into_sv(self, pthx: raw::Interpreter) -> SV {
@@ -435,7 +706,13 @@ impl IntoSV for bool {
 impl FromSV for bool {
     #[inline]
     unsafe fn from_sv(pthx: raw::Interpreter, raw: *mut raw::SV) -> bool {
-        pthx.sv_true(raw) != 0
+        get_magic(pthx, raw);
+        Self::from_sv_nomg(pthx, raw)
+    }
+
+    #[inline]
+    unsafe fn from_sv_nomg(pthx: raw::Interpreter, raw: *mut raw::SV) -> bool {
+        pthx.sv_2bool_flags(raw, 0) != 0
     }
 }
 
@@ -446,6 +723,7 @@ impl IntoSV for String {
     }
 }
 
+/// Create a character string. The UTF8 flag is always set.
 impl<'a> IntoSV for &'a str {
     #[inline]
     fn into_sv(self, pthx: raw::Interpreter) -> SV {
@@ -460,128 +738,32 @@ impl<'a> IntoSV for &'a str {
     }
 }
 
-impl IntoSV for SV {
+impl IntoSV for Box<str> {
     #[inline]
     fn into_sv(self, pthx: raw::Interpreter) -> SV {
-        assert!(self.pthx() == pthx);
-        self
+        (&*self).into_sv(pthx)
     }
 }
 
-impl<'a> IntoSV for &'a SV {
+impl<'a> IntoSV for Cow<'a, str> {
     #[inline]
     fn into_sv(self, pthx: raw::Interpreter) -> SV {
-        assert!(self.pthx() == pthx);
-        self.clone()
+        (&*self).into_sv(pthx)
     }
 }
 
-pthx! {
-    fn magic_free_any(_pthx, _sv: *mut raw::SV, magic: *mut raw::MAGIC) -> c_int {
-        unsafe {
-            let ptr = (*magic).mg_ptr as *mut Box<dyn Any>;
-            assert!(!ptr.is_null());
-            drop(Box::from_raw(ptr));
-        }
-        0
-    }
-}
-
-static VTBL_ANY: raw::MGVTBL = raw::MGVTBL {
-    svt_free: Some(magic_free_any),
-    ..raw::EMPTY_MGVTBL
-};
-
-impl IntoSV for Box<dyn Any> {
+impl IntoSV for SV {
+    #[inline]
     fn into_sv(self, pthx: raw::Interpreter) -> SV {
-        let sv = unsafe { SV::from_raw_owned(pthx, pthx.newSV(0)) };
-        sv.add_data(self);
-        sv.into_ref()
-    }
-}
-
-/// Reference to a Rust value stored inside the SV.
-///
-/// ```
-/// # #[macro_use] extern crate perl_xs;
-/// # #[macro_use] extern crate perl_sys;
-/// # use perl_xs::{SV, DataRef, convert::TryFromSV};
-/// # xs! {
-/// #   package Dummy;
-/// #   sub foo(ctx) {
-/// let value_sv: SV = ctx.new_sv_with_data(String::from("Hello world!"));
-/// let data_ref: DataRef<String> = value_sv.into_data_ref().and_then(DataRef::downcast).unwrap();
-/// assert_eq!(&**data_ref, "Hello world!");
-/// #   }
-/// # }
-/// # fn main() {}
-/// ```
-///
-/// Because Perl scalars are always shared, `DataRef` can provide only immutable references to the stored
-/// value. If mutable access is needed, value can be wrapped in a `RefCell`.
-///
-/// `DataRef` can be used as a type for a subroutine parameter:
-///
-/// ```
-/// # #[macro_use] extern crate perl_xs;
-/// # #[macro_use] extern crate perl_sys;
-/// # use std::cell::RefCell;
-/// # use perl_xs::{IV, DataRef};
-/// # xs! {
-/// #   package Dummy;
-/// sub increment(_ctx, value: DataRef<RefCell<IV>>, amount: IV) {
-///     *value.borrow_mut() += amount;
-/// }
-/// # }
-/// # fn main() {}
-/// ```
-///
-/// If `increment` is then called with a scalar that does not contain a `RefCell<IV>` value,
-/// perl exception will be thrown.
-pub struct DataRef<T: ?Sized> {
-    inner: *const T,
-    owner: SV,
-}
-
-impl DataRef<dyn Any> {
-    /// Attempt to downcast the ref to the concrete type while preserving owning SV.
-    pub fn downcast<T: 'static>(self) -> Option<DataRef<T>> {
-        let DataRef { inner, owner } = self;
-        unsafe {
-            (*inner).downcast_ref::<T>().map(|r| {
-                DataRef {
-                    inner: r,
-                    owner: owner,
-                }
-            })
-        }
-    }
-}
-
-impl<T: ?Sized> Deref for DataRef<T> {
-    type Target = T;
-
-    fn deref(&self) -> &T {
-        unsafe { &*self.inner }
-    }
-}
-
-impl TryFromSV for DataRef<dyn Any> {
-    type Error = &'static str;
-
-    unsafe fn try_from_sv(pthx: raw::Interpreter, sv: *mut raw::SV) -> Result<Self, Self::Error> {
-        let outer = SV::from_raw_borrowed(pthx, sv);
-        let inner = outer.deref().ok_or("not a reference")?;
-        inner.into_data_ref().ok_or("invalid value")
+        assert!(self.pthx() == pthx);
+        self
     }
 }
 
-impl<T: 'static> TryFromSV for DataRef<T> {
-    type Error = &'static str;
-
-    unsafe fn try_from_sv(pthx: raw::Interpreter, svp: *mut raw::SV) -> Result<Self, Self::Error> {
-        DataRef::<dyn Any>::try_from_sv(pthx, svp)?
-            .downcast()
-            .ok_or("invalid value")
+impl<'a> IntoSV for &'a SV {
+    #[inline]
+    fn into_sv(self, pthx: raw::Interpreter) -> SV {
+        assert!(self.pthx() == pthx);
+        self.clone()
     }
 }
@@ -0,0 +1,286 @@
+//! Tied hashes, arrays and scalars implemented in Rust.
+//!
+//! Implement one of the traits in this module for a Rust type and generate the tie methods with
+//! the [`tie!`](../macro.tie.html) macro. The macro defines `TIEHASH`, `FETCH`, `STORE` and the
+//! rest of the methods Perl calls on a tied variable. The Rust value is stored in the tie object
+//! the same way as values created with
+//! [`Context::new_sv_with_data`](../context/struct.Context.html#method.new_sv_with_data).
+//!
+//! ```
+//! # #[macro_use] extern crate perl_xs;
+//! # #[macro_use] extern crate perl_sys;
+//! mod store {
+//!     use std::collections::BTreeMap;
+//!     use std::ops::Bound;
+//!     use perl_xs::{Context, SV};
+//!     use perl_xs::tie::TieHash;
+//!
+//!     pub struct Store(BTreeMap<String, String>);
+//!
+//!     impl TieHash for Store {
+//!         type Value = String;
+//!
+//!         fn tie(_ctx: &mut Context, _args: Vec<SV>) -> Result<Self, String> {
+//!             Ok(Store(BTreeMap::new()))
+//!         }
+//!         fn fetch(&self, key: &str) -> Option<String> {
+//!             self.0.get(key).cloned()
+//!         }
+//!         fn store(&mut self, key: String, value: String) {
+//!             self.0.insert(key, value);
+//!         }
+//!         fn exists(&self, key: &str) -> bool {
+//!             self.0.contains_key(key)
+//!         }
+//!         fn delete(&mut self, key: &str) -> Option<String> {
+//!             self.0.remove(key)
+//!         }
+//!         fn clear(&mut self) {
+//!             self.0.clear();
+//!         }
+//!         fn first_key(&mut self) -> Option<String> {
+//!             self.0.keys().next().cloned()
+//!         }
+//!         fn next_key(&mut self, last: &str) -> Option<String> {
+//!             let range = (Bound::Excluded(last), Bound::Unbounded);
+//!             self.0.range::<str, _>(range).next().map(|(k, _)| k.clone())
+//!         }
+//!     }
+//!
+//!     tie! {
+//!         package Acme::Store;
+//!         hash Store;
+//!     }
+//! }
+//! # fn main() {}
+//! ```
+//!
+//! Generated methods are registered by adding the module to the bootstrap block, after which
+//! `tie my %h, 'Acme::Store'` works as expected.
+
+use std::cmp;
+
+use crate::context::Context;
+use crate::convert::{IntoSV, TryFromSV};
+use crate::raw::IV;
+use crate::SV;
+
+/// Rust type backing a tied hash.
+///
+/// See [`perltie`](http://perldoc.perl.org/perltie.html#Tying-Hashes) for the meaning of each
+/// method.
+pub trait TieHash: Sized + 'static {
+    /// Type of values stored in the hash.
+    type Value: TryFromSV + IntoSV;
+
+    /// Create a new instance, called by `tie %hash, $class, @args`.
+    fn tie(ctx: &mut Context, args: Vec<SV>) -> Result<Self, String>;
+
+    /// Return value stored under `key`, or `None` to return `undef`.
+    fn fetch(&self, key: &str) -> Option<Self::Value>;
+
+    /// Store `value` under `key`.
+    fn store(&mut self, key: String, value: Self::Value);
+
+    /// Return true if there is a value stored under `key`.
+    fn exists(&self, key: &str) -> bool;
+
+    /// Remove value stored under `key` and return it.
+    fn delete(&mut self, key: &str) -> Option<Self::Value>;
+
+    /// Remove all values.
+    fn clear(&mut self);
+
+    /// Return the first key, or `None` if the hash is empty.
+    fn first_key(&mut self) -> Option<String>;
+
+    /// Return the key following `last`, or `None` if there are no more keys.
+    fn next_key(&mut self, last: &str) -> Option<String>;
+}
+
+/// Rust type backing a tied array.
+///
+/// Only `fetch`, `store`, `fetch_size` and `store_size` are required, other methods have default
+/// implementations based on them. `store` must extend the array when the index is past the end.
+///
+/// Default implementations that move elements call `delete` for elements that `fetch` returns as
+/// `None`, so arrays that can have such holes must implement `delete` too.
+///
+/// See [`perltie`](http://perldoc.perl.org/perltie.html#Tying-Arrays) for the meaning of each
+/// method.
+pub trait TieArray: Sized + 'static {
+    /// Type of elements of the array.
+    type Value: TryFromSV + IntoSV;
+
+    /// Create a new instance, called by `tie @array, $class, @args`.
+    fn tie(ctx: &mut Context, args: Vec<SV>) -> Result<Self, String>;
+
+    /// Return element at `idx`, or `None` to return `undef`.
+    fn fetch(&self, idx: usize) -> Option<Self::Value>;
+
+    /// Store `value` at `idx`.
+    fn store(&mut self, idx: usize, value: Self::Value);
+
+    /// Return number of elements in the array.
+    fn fetch_size(&self) -> usize;
+
+    /// Grow or shrink the array to `size` elements.
+    fn store_size(&mut self, size: usize);
+
+    /// Prepare to grow the array to `size` elements. Does nothing by default.
+    fn extend(&mut self, _size: usize) {}
+
+    /// Remove all elements.
+    fn clear(&mut self) {
+        self.store_size(0);
+    }
+
+    /// Return true if there is an element at `idx`.
+    fn exists(&self, idx: usize) -> bool {
+        idx < self.fetch_size()
+    }
+
+    /// Remove element at `idx` and return it.
+    ///
+    /// Default implementation croaks, like `Tie::Array` does.
+    fn delete(&mut self, _idx: usize) -> Option<Self::Value> {
+        croak!("{} does not support delete", ::std::any::type_name::<Self>());
+    }
+
+    /// Append `values` to the end of the array.
+    fn push(&mut self, values: Vec<Self::Value>) {
+        let size = self.fetch_size();
+        for (i, value) in values.into_iter().enumerate() {
+            self.store(size + i, value);
+        }
+    }
+
+    /// Remove the last element and return it.
+    fn pop(&mut self) -> Option<Self::Value> {
+        let size = self.fetch_size();
+        if size == 0 {
+            return None;
+        }
+        let value = self.fetch(size - 1);
+        self.store_size(size - 1);
+        value
+    }
+
+    /// Remove the first element and return it.
+    fn shift(&mut self) -> Option<Self::Value> {
+        self.splice(0, 1, Vec::new()).pop().and_then(|value| value)
+    }
+
+    /// Insert `values` at the start of the array.
+    fn unshift(&mut self, values: Vec<Self::Value>) {
+        self.splice(0, 0, values);
+    }
+
+    /// Replace `length` elements starting at `offset` with `values` and return the removed
+    /// elements.
+    ///
+    /// `offset` and `length` are already resolved against the size of the array, but may point
+    /// past its end: such parts of the range are ignored.
+    fn splice(&mut self, offset: usize, length: usize, values: Vec<Self::Value>) -> Vec<Option<Self::Value>> {
+        let size = self.fetch_size();
+        let offset = cmp::min(offset, size);
+        let length = cmp::min(length, size - offset);
+        let removed = (offset..offset + length).map(|idx| self.fetch(idx)).collect();
+        let count = values.len();
+        if count > length {
+            let shift = count - length;
+            self.store_size(size + shift);
+            for idx in (offset + length..size).rev() {
+                move_element(self, idx, idx + shift);
+            }
+        } else if count < length {
+            let shift = length - count;
+            for idx in offset + length..size {
+                move_element(self, idx, idx - shift);
+            }
+            self.store_size(size - shift);
+        }
+        for (i, value) in values.into_iter().enumerate() {
+            self.store(offset + i, value);
+        }
+        removed
+    }
+}
+
+/// Copy element `from` to `to`, deleting `to` if `from` is a hole.
+fn move_element<T: TieArray + ?Sized>(array: &mut T, from: usize, to: usize) {
+    match array.fetch(from) {
+        Some(value) => array.store(to, value),
+        None => {
+            array.delete(to);
+        }
+    }
+}
+
+/// Rust type backing a tied scalar.
+///
+/// See [`perltie`](http://perldoc.perl.org/perltie.html#Tying-Scalars) for the meaning of each
+/// method.
+pub trait TieScalar: Sized + 'static {
+    /// Type of the value.
+    type Value: TryFromSV + IntoSV;
+
+    /// Create a new instance, called by `tie $scalar, $class, @args`.
+    fn tie(ctx: &mut Context, args: Vec<SV>) -> Result<Self, String>;
+
+    /// Return the value.
+    fn fetch(&self) -> Self::Value;
+
+    /// Replace the value.
+    fn store(&mut self, value: Self::Value);
+}
+
+#[doc(hidden)]
+pub fn tie<T: 'static>(
+    ctx: &mut Context,
+    class: String,
+    name: &str,
+    tie: fn(&mut Context, Vec<SV>) -> Result<T, String>,
+) -> SV {
+    let args = rest_args::<SV>(ctx, 1, name);
+    match tie(ctx, args) {
+        Ok(value) => ctx.new_sv_with_data(value).bless(&class),
+        Err(e) => croak!("{}: {}", name, e),
+    }
+}
+
+#[doc(hidden)]
+pub fn splice<T: TieArray>(ctx: &mut Context, this: &mut T, name: &str) -> Vec<SV> {
+    let size = this.fetch_size() as IV;
+    let items = ctx.st_items();
+    let offset = if items > 1 { arg::<IV>(ctx, 1, name) } else { 0 };
+    let offset = if offset < 0 { offset + size } else { offset };
+    if offset < 0 {
+        croak!("{}: offset {} before the start of the array", name, offset - size);
+    }
+    let length = if items > 2 { arg::<IV>(ctx, 2, name) } else { size - offset };
+    let length = if length < 0 { cmp::max(length + size - offset, 0) } else { length };
+    let values = rest_args(ctx, 3, name);
+    this.splice(offset as usize, length as usize, values)
+        .into_iter()
+        .map(|value| match value {
+            Some(value) => ctx.new_sv(value),
+            None => ctx.sv_undef(),
+        })
+        .collect()
+}
+
+fn arg<V: TryFromSV>(ctx: &mut Context, idx: isize, name: &str) -> V {
+    match ctx.st_try_fetch::<V>(idx) {
+        Some(Ok(v)) => v,
+        Some(Err(e)) => croak!("invalid argument {} for {}: {}", idx, name, e),
+        None => unreachable!(),
+    }
+}
+
+#[doc(hidden)]
+pub fn rest_args<V: TryFromSV>(ctx: &mut Context, from: isize, name: &str) -> Vec<V> {
+    (from..ctx.st_items())
+        .map(|idx| arg(ctx, idx, name))
+        .collect()
+}
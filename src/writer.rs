@@ -0,0 +1,177 @@
+use std::fmt;
+use std::io;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::slice;
+use std::str;
+
+use crate::raw;
+use crate::SV;
+
+/// Builder that writes a string directly into the buffer of a new SV.
+///
+/// Unlike building a `String` and converting it with `IntoSV`, this avoids copying the finished
+/// string, which matters for large outputs. The buffer is grown with
+/// [`SvGROW`](http://perldoc.perl.org/perlapi.html#SvGROW) as needed.
+///
+/// ```
+/// # #[macro_use] extern crate perl_xs;
+/// # #[macro_use] extern crate perl_sys;
+/// # use std::fmt::Write;
+/// # use perl_xs::AV;
+/// # xs! {
+/// #   package Dummy;
+/// sub join_numbers(ctx, list: AV) {
+///     let mut out = ctx.new_string_writer(1024);
+///     for n in list.iter::<perl_xs::IV>().flatten() {
+///         write!(out, "{},", n).unwrap();
+///     }
+///     out.finish_utf8().unwrap()
+/// }
+/// # }
+/// # fn main() {}
+/// ```
+pub struct StringWriter {
+    sv: SV,
+    buf: *mut u8,
+    len: usize,
+    cap: usize,
+}
+
+impl StringWriter {
+    pub(crate) fn new(pthx: raw::Interpreter, capacity: usize) -> StringWriter {
+        let sv = unsafe { SV::from_raw_owned(pthx, pthx.newSV(0)) };
+        let mut writer = StringWriter {
+            sv: sv,
+            buf: ptr::null_mut(),
+            len: 0,
+            cap: 0,
+        };
+        writer.reserve(capacity);
+        writer
+    }
+
+    /// Return number of bytes written so far.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Return true if nothing was written yet.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Make sure at least `additional` more bytes can be written without growing the buffer.
+    pub fn reserve(&mut self, additional: usize) {
+        let need = self.len + additional;
+        if need <= self.cap && !self.buf.is_null() {
+            return;
+        }
+        let cap = need.max(self.cap * 2).max(16);
+        unsafe {
+            // One extra byte for the trailing NUL.
+            self.buf = self.sv.pthx().sv_grow(self.sv.as_ptr(), (cap + 1) as raw::STRLEN) as *mut u8;
+        }
+        self.cap = cap;
+    }
+
+    /// Append bytes to the string.
+    #[inline]
+    pub fn push_bytes(&mut self, bytes: &[u8]) {
+        self.reserve(bytes.len());
+        unsafe {
+            ptr::copy_nonoverlapping(bytes.as_ptr(), self.buf.add(self.len), bytes.len());
+        }
+        self.len += bytes.len();
+    }
+
+    /// Extend the string by `n` zero bytes and return them for writing.
+    pub fn extend_zeroed(&mut self, n: usize) -> &mut [u8] {
+        self.reserve(n);
+        let start = self.len;
+        self.len += n;
+        unsafe {
+            let ptr = self.buf.add(start);
+            ptr::write_bytes(ptr, 0, n);
+            slice::from_raw_parts_mut(ptr, n)
+        }
+    }
+
+    /// Return the unused part of the buffer, as uninitialized bytes.
+    ///
+    /// After writing to it, mark the bytes as part of the string with [`set_len`](#method.set_len).
+    #[inline]
+    pub fn spare_capacity_mut(&mut self) -> &mut [MaybeUninit<u8>] {
+        unsafe { slice::from_raw_parts_mut(self.buf.add(self.len) as *mut MaybeUninit<u8>, self.cap - self.len) }
+    }
+
+    /// Set length of the string.
+    ///
+    /// This method is unsafe, because bytes up to `len` must have been written.
+    ///
+    /// Panics if `len` exceeds the reserved capacity.
+    #[inline]
+    pub unsafe fn set_len(&mut self, len: usize) {
+        assert!(len <= self.cap, "length exceeds reserved capacity");
+        self.len = len;
+    }
+
+    /// Return the string written so far.
+    #[inline]
+    pub fn as_bytes(&self) -> &[u8] {
+        unsafe { slice::from_raw_parts(self.buf, self.len) }
+    }
+
+    /// Finish writing and return the SV holding a byte string.
+    pub fn finish(self) -> SV {
+        self.into_sv(false)
+    }
+
+    /// Finish writing and return the SV holding a character string.
+    ///
+    /// Fails if the bytes written are not valid UTF-8. The UTF8 flag is set only if the string is
+    /// not ASCII.
+    pub fn finish_utf8(self) -> Result<SV, str::Utf8Error> {
+        let utf8 = !str::from_utf8(self.as_bytes())?.is_ascii();
+        Ok(self.into_sv(utf8))
+    }
+
+    fn into_sv(self, utf8: bool) -> SV {
+        let pthx = self.sv.pthx();
+        let raw = self.sv.as_ptr();
+        unsafe {
+            *self.buf.add(self.len) = 0;
+            pthx.ouroboros_sv_cur_set(raw, self.len as raw::STRLEN);
+            // SvPOK_only
+            (*raw).sv_flags &= !(raw::SVf_OK | raw::SVf_IVisUV | raw::SVf_UTF8);
+            (*raw).sv_flags |= raw::SVf_POK | raw::SVp_POK;
+            if utf8 {
+                (*raw).sv_flags |= raw::SVf_UTF8;
+            }
+        }
+        self.sv
+    }
+}
+
+impl io::Write for StringWriter {
+    #[inline]
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.push_bytes(buf);
+        Ok(buf.len())
+    }
+
+    #[inline]
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl fmt::Write for StringWriter {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push_bytes(s.as_bytes());
+        Ok(())
+    }
+}
@@ -0,0 +1,59 @@
+use std::cmp::Ordering;
+use perl_xs::{ AV, IV, SV, G_DISCARD };
+
+xs! {
+    package XSTest::Callback;
+
+    sub first(ctx, block: SV, items: AV) {
+        let mut callback = ctx.new_callback(&block);
+        items.iter::<SV>().flatten().find(|item| callback.call_topic(item).is_true())
+    }
+
+    sub count(ctx, block: SV, items: AV) {
+        let mut callback = ctx.new_callback(&block);
+        items.iter::<SV>().flatten().filter(|item| callback.call_topic(item).is_true()).count() as IV
+    }
+
+    sub apply(ctx, block: SV, items: AV) {
+        let mut callback = ctx.new_callback(&block);
+        for item in items.iter::<SV>().flatten() {
+            callback.call_topic(&item);
+        }
+    }
+
+    sub map(ctx, block: SV, items: AV) {
+        let mut callback = ctx.new_callback(&block);
+        items.iter::<SV>().flatten().map(|item| callback.call_topic(&item)).collect::<Vec<SV>>()
+    }
+
+    sub reduce(ctx, block: SV, items: AV) {
+        let mut callback = ctx.new_callback(&block);
+        let mut iter = items.iter::<SV>().flatten();
+        let first = iter.next();
+        first.map(|first| iter.fold(first, |acc, item| callback.call_pair(&acc, &item)))
+    }
+
+    sub sort_by(ctx, block: SV, items: AV) {
+        let mut callback = ctx.new_callback(&block);
+        let mut list: Vec<SV> = items.iter::<SV>().flatten().collect();
+        list.sort_by(|a, b| callback.call_pair(a, b).iv().cmp(&0));
+        list
+    }
+
+    sub max_by(ctx, block: SV, items: AV) {
+        let mut callback = ctx.new_callback(&block);
+        items.iter::<SV>().flatten().max_by(|a, b| match callback.call_pair(a, b).iv() {
+            n if n < 0 => Ordering::Less,
+            0 => Ordering::Equal,
+            _ => Ordering::Greater,
+        })
+    }
+
+    sub topic_len(ctx) {
+        ctx.get_sv(cstr!("_")).map(|sv| sv.to_string().unwrap().len() as IV)
+    }
+
+    sub call_thrower(ctx) {
+        ctx.call_pv(cstr!("XSTest::Callback::thrower"), G_DISCARD);
+    }
+}
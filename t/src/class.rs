@@ -0,0 +1,33 @@
+use perl_xs::{IV, G_DISCARD};
+use perl_xs::class::{Object, ObjectMut};
+
+#[derive(FromPerlKV, PerlClass)]
+#[perlxs(package = "XSTest::Class")]
+pub struct Counter {
+    #[perlxs(set)]
+    value: IV,
+    #[perlxs(get)]
+    name: Option<String>,
+}
+
+xs! {
+    package XSTest::Class;
+
+    sub inc(ctx, this: ObjectMut<Counter>) {
+        this.value += 1;
+    }
+
+    sub with_callback(ctx, this: Object<Counter>) {
+        ctx.call_pv(cstr!("XSTest::Class::callback"), G_DISCARD);
+        this.value
+    }
+}
+
+#[derive(FromPerlKV, PerlClass, Clone)]
+#[perlxs(package = "XSTest::Class::Point", clone)]
+pub struct Point {
+    #[perlxs(get)]
+    x: IV,
+    #[perlxs(get)]
+    y: IV,
+}
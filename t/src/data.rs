@@ -15,4 +15,73 @@ xs! {
     sub inc(_ctx, this: DataRef<RefCell<IV>>) {
         *this.borrow_mut() += 1;
     }
+
+    sub get_or(_ctx, this: Option<DataRef<RefCell<IV>>>, default: IV) {
+        this.map_or(default, |this| *this.borrow())
+    }
+}
+
+pub mod mutable {
+    use perl_xs::{ IV, DataRef, DataRefMut, G_DISCARD };
+
+    xs! {
+        package XSTest::DataMut;
+
+        sub new(ctx, class: String, initial: IV) {
+            ctx.new_sv_with_data(initial).bless(&class)
+        }
+
+        sub get(_ctx, this: DataRef<IV>) {
+            *this
+        }
+
+        sub inc(_ctx, this: DataRefMut<IV>) {
+            *this += 1;
+        }
+
+        sub inc_with_callback(ctx, this: DataRefMut<IV>) {
+            *this += 1;
+            ctx.call_pv(cstr!("XSTest::DataMut::callback"), G_DISCARD);
+        }
+    }
+}
+
+pub mod attach {
+    use perl_xs::{ IV, SV, DataRef, DataRefMut };
+
+    pub struct Label(String);
+
+    xs! {
+        package XSTest::Attach;
+
+        sub new(ctx, class: String, id: IV, label: String) {
+            let sv = ctx.new_sv(0 as IV);
+            sv.attach(id);
+            sv.attach(Label(label));
+            sv.into_ref().bless(&class)
+        }
+
+        sub id(_ctx, this: DataRef<IV>) {
+            *this
+        }
+
+        sub label(_ctx, this: DataRef<Label>) {
+            this.0.clone()
+        }
+
+        sub set_label(_ctx, this: DataRefMut<Label>, label: String) {
+            this.0 = label;
+        }
+
+        sub relabel(_ctx, this: SV, label: String) {
+            let obj = this.deref().unwrap();
+            let old = obj.detach::<Label>().map(|l| l.0);
+            obj.attach(Label(label));
+            old
+        }
+
+        sub has_foreign(_ctx, this: SV) {
+            this.deref().unwrap().has_foreign_data::<Label>()
+        }
+    }
 }
@@ -15,15 +15,41 @@ mod panic;
 mod param;
 mod data;
 mod derive;
+mod class;
+mod tie;
+mod magic;
+mod overload;
+mod scope;
+mod callback;
 
 xs! {
     bootstrap boot_XSTest;
     use stack;
     use scalar;
+    use scalar::pinned;
+    use scalar::writer;
+    use scalar::backed;
+    use scalar::primitive;
+    use scalar::alias;
     use array;
     use hash;
     use panic;
     use param;
     use data;
+    use data::mutable;
+    use data::attach;
     use derive;
+    use class;
+    use class::Counter;
+    use class::Point;
+    use tie::hash;
+    use tie::array;
+    use tie::sparse;
+    use tie::scalar;
+    use magic;
+    use overload;
+    use overload::ops;
+    use scope;
+    use callback;
+    boot overload::ops;
 }
@@ -0,0 +1,39 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use perl_xs::{ IV, SV };
+use perl_xs::magic::ScalarMagic;
+
+thread_local! {
+    static VALUE: Rc<Cell<IV>> = Rc::new(Cell::new(0));
+    static FREED: Cell<IV> = Cell::new(0);
+}
+
+xs! {
+    package XSTest::Magic;
+
+    sub init(ctx) {
+        let get = VALUE.with(|v| v.clone());
+        let set = VALUE.with(|v| v.clone());
+        ctx.get_sv_add(cstr!("XSTest::Magic::counter")).add_magic(
+            ScalarMagic::new()
+                .get(move || { get.set(get.get() + 1); get.get() })
+                .set(move |value: IV| set.set(value * 10))
+        );
+    }
+
+    sub value(_ctx) {
+        VALUE.with(|v| v.get())
+    }
+
+    sub add_free_magic(_ctx, sv: SV) {
+        sv.add_magic(ScalarMagic::new().free(|| FREED.with(|f| f.set(f.get() + 1))));
+    }
+
+    sub add_croaking_magic(_ctx, sv: SV) {
+        sv.add_magic(ScalarMagic::new().get(|| -> IV { croak!("no value here") }));
+    }
+
+    sub freed(_ctx) {
+        FREED.with(|f| f.get())
+    }
+}
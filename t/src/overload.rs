@@ -0,0 +1,50 @@
+use perl_xs::IV;
+
+pub struct Money(IV);
+
+xs! {
+    package XSTest::Money;
+
+    sub new(ctx, class: String, cents: IV) {
+        ctx.new_sv_with_data(Money(cents)).bless(&class)
+    }
+}
+
+pub mod ops {
+    use perl_xs::{ DataRef, IV, NV, SV };
+    use super::Money;
+
+    fn cents(sv: &SV) -> IV {
+        match sv.deref().and_then(|inner| inner.attached::<Money>()) {
+            Some(money) => money.0,
+            None => (sv.nv() * 100.0).round() as IV,
+        }
+    }
+
+    overload! {
+        package XSTest::Money;
+        fallback = true;
+
+        "\"\"" => sub to_string(ctx, this: DataRef<Money>) {
+            format!("{}.{:02}", this.0 / 100, this.0 % 100)
+        }
+
+        "0+" => sub to_number(ctx, this: DataRef<Money>) {
+            this.0 as NV / 100.0
+        }
+
+        "bool" => sub to_bool(ctx, this: DataRef<Money>) {
+            this.0 != 0
+        }
+
+        "+" => sub add(ctx, this: DataRef<Money>, other: SV) {
+            let total = this.0 + cents(&other);
+            ctx.new_sv_with_data(Money(total)).bless("XSTest::Money")
+        }
+
+        "<=>" => sub compare(ctx, this: DataRef<Money>, other: SV, swapped: bool) {
+            let ord = this.0.cmp(&cents(&other)) as IV;
+            if swapped { -ord } else { ord }
+        }
+    }
+}
@@ -3,6 +3,7 @@ use perl_xs::{ IV, AV };
 xs! {
     package XSTest::Param;
 
+    /// Add two numbers.
     sub add(ctx, a: IV, b: IV) {
         a + b
     }
@@ -11,6 +12,9 @@ xs! {
         a.top_index() + 1
     }
 
+    /// Count characters in a string.
+    ///
+    /// Byte strings are treated as Latin-1.
     sub strlen(ctx, s: String) {
         s.chars().count() as IV
     }
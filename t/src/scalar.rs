@@ -1,4 +1,6 @@
-use perl_xs::{ IV, NV, SV };
+use std::borrow::Cow;
+use perl_xs::{ AV, Bytes, HV, IV, NV, SV };
+use perl_xs::convert::{Raw, Strict};
 
 xs! {
     package XSTest;
@@ -25,6 +27,72 @@ xs! {
         )
     }
 
+    sub test_ref_info(ctx, sv: SV) {
+        (
+            sv.refaddr().map(|addr| addr as IV),
+            sv.reftype(),
+            sv.blessed(),
+            sv.is_weak(),
+        )
+    }
+
+    sub test_weaken(ctx, sv: SV) {
+        sv.weaken();
+        sv.is_weak()
+    }
+
+    sub test_conv_iv(ctx, iv: IV) {
+        iv
+    }
+
+    sub test_conv_opt_iv(ctx, iv: Option<IV>) {
+        iv
+    }
+
+    sub test_conv_opt_str(ctx, s: Option<String>) {
+        s
+    }
+
+    sub test_conv_opt_u8(ctx, v: Option<u8>) {
+        v
+    }
+
+    sub test_conv_opt_kinds(ctx, av: Option<AV>, hv: Option<HV>, sv: Option<SV>) {
+        (av.map(|av| av.top_index() + 1), hv.is_some(), sv.is_some())
+    }
+
+    sub test_conv_nv(ctx, nv: NV) {
+        nv
+    }
+
+    sub test_conv_str(ctx, s: String) {
+        s
+    }
+
+    sub test_conv_bool(ctx, b: bool) {
+        b
+    }
+
+    sub test_conv_strict_iv(ctx, iv: Strict<IV>) {
+        iv
+    }
+
+    sub test_conv_strict_nv(ctx, nv: Strict<NV>) {
+        nv
+    }
+
+    sub test_conv_strict_u8(ctx, v: Strict<u8>) {
+        v
+    }
+
+    sub test_conv_raw_iv(ctx, iv: Raw<IV>) {
+        iv.0
+    }
+
+    sub test_conv_raw_str(ctx, s: Raw<String>) {
+        s.0
+    }
+
     sub test_unicode(ctx, sv: SV) {
         let utf8: IV = if sv.utf8() { 1 } else { 0 };
 
@@ -34,6 +102,51 @@ xs! {
         }
     }
 
+    sub test_str(ctx, s: String) {
+        (s.chars().count() as IV, s)
+    }
+
+    sub test_as_str(ctx, sv: SV) {
+        match unsafe { sv.as_str() } {
+            Ok(Cow::Borrowed(s)) => (Some(s.to_owned()), 1 as IV),
+            Ok(Cow::Owned(s)) => (Some(s), 0 as IV),
+            Err(_) => (None, -1 as IV),
+        }
+    }
+
+    sub test_cow_str(ctx, s: Cow<str>) {
+        let borrowed: IV = if let Cow::Borrowed(_) = s { 1 } else { 0 };
+        (s.into_owned(), borrowed)
+    }
+
+    sub test_utf8_upgrade(ctx, sv: SV) {
+        sv.utf8_upgrade();
+    }
+
+    sub test_utf8_downgrade(ctx, sv: SV) {
+        sv.utf8_downgrade()
+    }
+
+    sub test_utf8_encode(ctx, sv: SV) {
+        sv.utf8_encode();
+    }
+
+    sub test_utf8_decode(ctx, sv: SV) {
+        sv.utf8_decode()
+    }
+
+    sub test_bytes(ctx, b: Bytes) {
+        (b.len() as IV, b)
+    }
+
+    sub test_vec_u8(ctx, v: Vec<u8>) {
+        v
+    }
+
+    sub test_byte_slice(ctx) {
+        &b"\x00\xffbinary"[..]
+    }
+
     sub test_new_sv_iv(ctx, iv: IV) {
         ctx.new_sv(iv)
     }
@@ -46,3 +159,189 @@ xs! {
         ctx.sv_undef()
     }
 }
+
+pub mod pinned {
+    use perl_xs::{ IV, SV, G_DISCARD };
+
+    xs! {
+        package XSTest::Pinned;
+
+        sub count_with_callback(ctx, sv: SV) {
+            let text = sv.pin();
+            ctx.call_pv(cstr!("XSTest::Pinned::callback"), G_DISCARD);
+            (text.len() as IV, text.as_str().unwrap().into_owned())
+        }
+
+        sub nested_pins(ctx, sv: SV) {
+            let outer = sv.pin();
+            let inner = sv.pin();
+            (outer.as_str().unwrap().into_owned(), inner.utf8(), sv.is_readonly())
+        }
+    }
+}
+
+pub mod writer {
+    use std::fmt::Write as FmtWrite;
+    use std::io::Write as IoWrite;
+    use perl_xs::IV;
+
+    xs! {
+        package XSTest::Writer;
+
+        sub repeat(ctx, s: String, n: IV) {
+            let mut out = ctx.new_string_writer(0);
+            for i in 0..n {
+                write!(out, "{}{}", s, i).unwrap();
+            }
+            out.finish_utf8().unwrap()
+        }
+
+        sub binary(ctx, n: IV) {
+            let mut out = ctx.new_string_writer(4);
+            out.write_all(b"\xff\x00").unwrap();
+            out.extend_zeroed(n as usize)[0] = 1;
+            out.finish()
+        }
+
+        sub invalid_utf8(ctx) {
+            let mut out = ctx.new_string_writer(4);
+            out.push_bytes(b"\xff");
+            out.finish_utf8().is_err()
+        }
+    }
+}
+
+pub mod backed {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use perl_xs::IV;
+
+    static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+    pub struct Buffer(Vec<u8>);
+
+    impl AsRef<[u8]> for Buffer {
+        fn as_ref(&self) -> &[u8] {
+            &self.0
+        }
+    }
+
+    impl Drop for Buffer {
+        fn drop(&mut self) {
+            DROPPED.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    xs! {
+        package XSTest::Backed;
+
+        sub bytes(ctx, size: IV) {
+            let mut buf = vec![b'x'; size as usize];
+            buf.push(0);
+            ctx.new_sv_backed_by(Buffer(buf))
+        }
+
+        sub table(ctx) {
+            ctx.new_sv_backed_by_str("ключ\0")
+        }
+
+        sub unterminated(ctx) {
+            ctx.new_sv_backed_by(&b"xyz"[..])
+        }
+
+        sub dropped(_ctx) {
+            DROPPED.load(Ordering::SeqCst) as IV
+        }
+    }
+}
+
+pub mod primitive {
+    use std::borrow::Cow;
+    use std::ffi::OsString;
+    use std::path::PathBuf;
+    use std::rc::Rc;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use perl_xs::IV;
+    use perl_xs::convert::BigInt;
+
+    xs! {
+        package XSTest::Primitive;
+
+        sub to_i8(ctx, v: i8) { v }
+        sub to_i16(ctx, v: i16) { v }
+        sub to_i32(ctx, v: i32) { v }
+        sub to_isize(ctx, v: isize) { v }
+        sub to_u8(ctx, v: u8) { v }
+        sub to_u16(ctx, v: u16) { v }
+        sub to_u32(ctx, v: u32) { v }
+        sub to_usize(ctx, v: usize) { v }
+        sub to_f32(ctx, v: f32) { v }
+        sub to_char(ctx, v: char) { v }
+        sub to_boxed_str(ctx, v: Box<str>) { v }
+        sub to_cow(ctx, v: Cow<str>) { v }
+        sub to_os_string(ctx, v: OsString) { v }
+        sub to_path(ctx, v: PathBuf) { v.join("file") }
+        sub to_rc(ctx, v: Rc<u8>) { v }
+        sub to_arc_str(ctx, v: Arc<str>) { v }
+        sub to_duration(ctx, v: Duration) { v }
+
+        sub shared_twice(ctx, v: Rc<String>) {
+            (v.clone(), v)
+        }
+
+        sub duration_millis(ctx, v: Duration) {
+            v.as_millis() as u32
+        }
+
+        sub to_i128(ctx, v: i128) { v }
+        sub to_u128(ctx, v: u128) { v }
+        sub to_bigint(ctx, v: BigInt<i128>) { v }
+
+        sub add_i128(ctx, a: i128, b: i128) {
+            BigInt(a + b)
+        }
+
+        sub bigint_list(ctx, v: i128) {
+            (1 as IV, "two", BigInt(v))
+        }
+
+        sub bigint_vec(ctx, v: i128) {
+            vec![BigInt(v), BigInt(-1), BigInt(v)]
+        }
+    }
+}
+
+pub mod alias {
+    use perl_xs::{ IV, NV, SV, UV };
+    use perl_xs::convert::Alias;
+
+    xs! {
+        package XSTest::Alias;
+
+        sub read_into(ctx, buf: Alias, len: IV) {
+            buf.set_str(&"x".repeat(len as usize));
+            len
+        }
+
+        sub chomp_it(ctx, s: Alias) {
+            let value: String = s.to_string().unwrap();
+            let trimmed = value.trim_end_matches('\n');
+            let removed = (value.len() - trimmed.len()) as IV;
+            s.set_str(trimmed);
+            removed
+        }
+
+        sub set_kind(ctx, sv: SV, kind: String) {
+            match &kind[..] {
+                "iv" => sv.set_iv(-42),
+                "uv" => sv.set_uv(UV::max_value()),
+                "nv" => sv.set_nv(0.5 as NV),
+                "str" => sv.set_str("ключ"),
+                "bytes" => sv.set(perl_xs::Bytes(vec![0xff])),
+                "undef" => sv.set_undef(),
+                "sv" => sv.set_sv(&ctx.new_sv("copied")),
+                _ => panic!("unknown kind {}", kind),
+            }
+        }
+    }
+}
@@ -0,0 +1,92 @@
+use perl_xs::{ AV, HV, IV, SV, G_DISCARD };
+
+fn new_tracked(data: SV) {
+    data.bless("XSTest::Scope::Tracked").into_mortal();
+}
+
+xs! {
+    package XSTest::Scope;
+
+    sub without_scope(ctx, n: IV) {
+        for _ in 0..n {
+            new_tracked(ctx.new_sv_with_data(()));
+            ctx.call_pv(cstr!("XSTest::Scope::checkpoint"), G_DISCARD);
+        }
+    }
+
+    sub with_scope(ctx, n: IV) {
+        let mut scope = ctx.new_scope();
+        for _ in 0..n {
+            new_tracked(scope.new_sv_with_data(()));
+            scope.call_pv(cstr!("XSTest::Scope::checkpoint"), G_DISCARD);
+            scope.free_tmps();
+        }
+    }
+
+    sub scope_end(ctx, n: IV) {
+        {
+            let mut scope = ctx.new_scope();
+            for _ in 0..n {
+                new_tracked(scope.new_sv_with_data(()));
+            }
+        }
+        ctx.call_pv(cstr!("XSTest::Scope::checkpoint"), G_DISCARD);
+    }
+
+    sub owned_survives(ctx) {
+        let sv = {
+            let mut scope = ctx.new_scope();
+            scope.new_sv("kept")
+        };
+        sv
+    }
+
+    sub local_var(ctx) {
+        {
+            let mut scope = ctx.new_scope();
+            scope.local_scalar(cstr!("XSTest::Scope::var")).set_str("inner");
+            scope.call_pv(cstr!("XSTest::Scope::observe"), G_DISCARD);
+        }
+        ctx.call_pv(cstr!("XSTest::Scope::observe"), G_DISCARD);
+    }
+
+    sub local_topic(ctx, items: AV) {
+        let mut scope = ctx.new_scope();
+        let topic = scope.local_scalar(cstr!("_"));
+        for item in items.iter::<SV>().flatten() {
+            topic.set_sv(&item);
+            scope.call_pv(cstr!("XSTest::Scope::observe_topic"), G_DISCARD);
+        }
+    }
+
+    sub alias_topic(ctx, items: AV) {
+        for item in items.iter::<SV>().flatten() {
+            let mut scope = ctx.new_scope();
+            scope.local_alias(cstr!("_"), &item);
+            scope.call_pv(cstr!("XSTest::Scope::modify_topic"), G_DISCARD);
+        }
+    }
+
+    sub local_containers(ctx) {
+        let mut scope = ctx.new_scope();
+        scope.local_array(cstr!("XSTest::Scope::list")).push(scope.new_sv("local"));
+        scope.local_hash(cstr!("XSTest::Scope::map")).store("key", scope.new_sv("local"));
+        scope.call_pv(cstr!("XSTest::Scope::observe_containers"), G_DISCARD);
+    }
+
+    sub local_helem(ctx, hv: HV) {
+        let mut scope = ctx.new_scope();
+        scope.local_helem(&hv, "key").set_str("local");
+        scope.local_helem(&hv, "new").set_str("local");
+        scope.call_pv(cstr!("XSTest::Scope::observe_containers"), G_DISCARD);
+    }
+
+    sub local_value(ctx, sv: SV) {
+        {
+            let mut scope = ctx.new_scope();
+            scope.local_value(&sv);
+            sv.set_str("temporary");
+            scope.call_pv(cstr!("XSTest::Scope::observe"), G_DISCARD);
+        }
+    }
+}
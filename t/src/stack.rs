@@ -1,4 +1,5 @@
-use perl_xs::{ IV, UV, NV };
+use perl_xs::{ IV, UV, NV, SV };
+use perl_xs::context::List;
 
 xs! {
     package XSTest;
@@ -19,4 +20,65 @@ xs! {
         ctx.st_push("Nu intra i\u{0302}n panica\u{0306}");
         ctx.st_push("😱❌");
     }
+
+    sub test_push_vec(ctx, n: IV) {
+        (0..n).collect::<Vec<IV>>()
+    }
+
+    sub test_push_slice(ctx) {
+        &["foo", "bar", "baz"][..]
+    }
+
+    sub test_push_list(ctx, n: IV) {
+        List((0..n).map(|i| format!("item{}", i)))
+    }
+
+    sub test_push_filtered(ctx, n: IV) {
+        List((0..n).filter(|i| i % 3 == 0))
+    }
+
+    sub test_push_args(ctx) {
+        List((0..ctx.st_items()).map(|i| ctx.st_fetch::<SV>(i).unwrap()).collect::<Vec<_>>())
+    }
+
+    sub test_st_iter(ctx) {
+        let strings: Vec<String> = ctx.st_iter::<String>().collect();
+        strings.join(",")
+    }
+
+    sub test_st_iter_len(ctx) {
+        ctx.st_iter::<SV>().len() as IV
+    }
+
+    sub test_double_in_place(ctx) {
+        let items = ctx.st_items();
+        for i in 0..items {
+            let v: IV = ctx.st_fetch(i).unwrap();
+            ctx.st_store(i, v * 2);
+        }
+        ctx.st_return(items);
+    }
+
+    sub test_return_first(ctx, n: IV) {
+        ctx.st_return(n as isize);
+    }
+
+    sub test_return_extra(ctx) {
+        ctx.st_extend(3);
+        ctx.st_store(0, "a");
+        ctx.st_store(1, "b");
+        ctx.st_store(2, "c");
+        ctx.st_return(3);
+        "d"
+    }
+
+    sub test_store_past_args(ctx) {
+        ctx.st_store(2, "c");
+        ctx.st_store(3, "d");
+        ctx.st_return(4);
+    }
+
+    sub test_store_gap(ctx) {
+        ctx.st_store(2, "c");
+    }
 }
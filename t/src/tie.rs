@@ -0,0 +1,173 @@
+pub mod hash {
+    use std::collections::BTreeMap;
+    use std::ops::Bound;
+    use perl_xs::{ Context, SV };
+    use perl_xs::tie::TieHash;
+
+    pub struct Store(BTreeMap<String, String>);
+
+    impl TieHash for Store {
+        type Value = String;
+
+        fn tie(_ctx: &mut Context, args: Vec<SV>) -> Result<Self, String> {
+            let mut map = BTreeMap::new();
+            for pair in args.chunks(2) {
+                let key = pair[0].to_string().map_err(|e| e.to_string())?;
+                let value = match pair.get(1) {
+                    Some(sv) => sv.to_string().map_err(|e| e.to_string())?,
+                    None => return Err("odd number of arguments".to_owned()),
+                };
+                map.insert(key, value);
+            }
+            Ok(Store(map))
+        }
+
+        fn fetch(&self, key: &str) -> Option<String> {
+            self.0.get(key).cloned()
+        }
+
+        fn store(&mut self, key: String, value: String) {
+            self.0.insert(key, value);
+        }
+
+        fn exists(&self, key: &str) -> bool {
+            self.0.contains_key(key)
+        }
+
+        fn delete(&mut self, key: &str) -> Option<String> {
+            self.0.remove(key)
+        }
+
+        fn clear(&mut self) {
+            self.0.clear();
+        }
+
+        fn first_key(&mut self) -> Option<String> {
+            self.0.keys().next().cloned()
+        }
+
+        fn next_key(&mut self, last: &str) -> Option<String> {
+            let range = (Bound::Excluded(last), Bound::Unbounded);
+            self.0.range::<str, _>(range).next().map(|(k, _)| k.clone())
+        }
+    }
+
+    tie! {
+        package XSTest::Tie::Hash;
+        hash Store;
+    }
+}
+
+pub mod array {
+    use perl_xs::{ Context, IV, SV };
+    use perl_xs::tie::TieArray;
+
+    pub struct Numbers(Vec<IV>);
+
+    impl TieArray for Numbers {
+        type Value = IV;
+
+        fn tie(_ctx: &mut Context, _args: Vec<SV>) -> Result<Self, String> {
+            Ok(Numbers(Vec::new()))
+        }
+
+        fn fetch(&self, idx: usize) -> Option<IV> {
+            self.0.get(idx).cloned()
+        }
+
+        fn store(&mut self, idx: usize, value: IV) {
+            if idx >= self.0.len() {
+                self.0.resize(idx + 1, 0);
+            }
+            self.0[idx] = value;
+        }
+
+        fn fetch_size(&self) -> usize {
+            self.0.len()
+        }
+
+        fn store_size(&mut self, size: usize) {
+            self.0.resize(size, 0);
+        }
+    }
+
+    tie! {
+        package XSTest::Tie::Array;
+        array Numbers;
+    }
+}
+
+pub mod sparse {
+    use perl_xs::{ Context, IV, SV };
+    use perl_xs::tie::TieArray;
+
+    pub struct Sparse(Vec<Option<IV>>);
+
+    impl TieArray for Sparse {
+        type Value = IV;
+
+        fn tie(_ctx: &mut Context, _args: Vec<SV>) -> Result<Self, String> {
+            Ok(Sparse(Vec::new()))
+        }
+
+        fn fetch(&self, idx: usize) -> Option<IV> {
+            self.0.get(idx).cloned().and_then(|value| value)
+        }
+
+        fn store(&mut self, idx: usize, value: IV) {
+            if idx >= self.0.len() {
+                self.0.resize(idx + 1, None);
+            }
+            self.0[idx] = Some(value);
+        }
+
+        fn fetch_size(&self) -> usize {
+            self.0.len()
+        }
+
+        fn store_size(&mut self, size: usize) {
+            self.0.resize(size, None);
+        }
+
+        fn exists(&self, idx: usize) -> bool {
+            self.0.get(idx).map_or(false, Option::is_some)
+        }
+
+        fn delete(&mut self, idx: usize) -> Option<IV> {
+            self.0.get_mut(idx).and_then(Option::take)
+        }
+    }
+
+    tie! {
+        package XSTest::Tie::Sparse;
+        array Sparse;
+    }
+}
+
+pub mod scalar {
+    use perl_xs::{ Context, IV, SV };
+    use perl_xs::tie::TieScalar;
+
+    pub struct Counter(IV);
+
+    impl TieScalar for Counter {
+        type Value = IV;
+
+        fn tie(_ctx: &mut Context, args: Vec<SV>) -> Result<Self, String> {
+            Ok(Counter(args.first().map(|sv| sv.iv()).unwrap_or(0)))
+        }
+
+        fn fetch(&self) -> IV {
+            self.0
+        }
+
+        fn store(&mut self, value: IV) {
+            self.0 = value;
+        }
+    }
+
+    tie! {
+        package XSTest::Tie::Scalar;
+        scalar Counter;
+    }
+}
@@ -0,0 +1,82 @@
+use strict;
+use warnings;
+
+use Test::More;
+use Test::Fatal;
+
+require_ok("XSTest");
+
+is XSTest::Callback::first(sub { $_ > 2 }, [ 1 .. 5 ]), 3, "first";
+is XSTest::Callback::first(sub { $_ > 10 }, [ 1 .. 5 ]), undef, "first without a match";
+is XSTest::Callback::count(sub { $_ % 2 }, [ 1 .. 9 ]), 5, "count";
+
+{
+    my @items = (1, 2, 3);
+    XSTest::Callback::apply(sub { $_ *= 10 }, \@items);
+    is_deeply \@items, [ 10, 20, 30 ], "\$_ is an alias";
+}
+
+is_deeply [ XSTest::Callback::map(sub { $_ * 2 }, [ 1, 2, 3 ]) ], [ 2, 4, 6 ], "results are distinct values";
+is_deeply [ XSTest::Callback::map(sub { "<$_>" }, [ qw/a b/ ]) ], [ "<a>", "<b>" ], "string results are distinct values";
+
+is XSTest::Callback::reduce(sub { $a + $b }, [ 1 .. 10 ]), 55, "reduce";
+is XSTest::Callback::reduce(sub { $a . $b }, [ qw/a b c/ ]), "abc", "reduce strings";
+is XSTest::Callback::reduce(sub { die }, []), undef, "reduce empty list";
+
+is_deeply [ XSTest::Callback::sort_by(sub { $b <=> $a }, [ 3, 10, 1, 7 ]) ], [ 10, 7, 3, 1 ], "sort with \$a and \$b";
+is XSTest::Callback::max_by(sub { length($a) <=> length($b) }, [ qw/aa aaaa a/ ]), "aaaa", "max_by";
+
+{
+    package Other;
+    ::is XSTest::Callback::reduce(sub { $a * $b }, [ 1 .. 5 ]), 120, "\$a and \$b of the calling package";
+}
+
+{
+    local $_ = "topic";
+    our ($a, $b) = ("a", "b");
+    XSTest::Callback::first(sub { 0 }, [ 1, 2 ]);
+    XSTest::Callback::reduce(sub { 0 }, [ 1, 2 ]);
+    is $_, "topic", "\$_ restored";
+    is "$a$b", "ab", "\$a and \$b restored";
+}
+
+{
+    my $calls = 0;
+    my $res = XSTest::Callback::first(sub { $calls++; $_ eq "x" }, [ "a", "x", "b" ]);
+    is $calls, 2, "stops at the first match";
+}
+
+like exception { XSTest::Callback::first("not code", [ 1 ]) }, qr/not a code reference/, "not a code reference";
+like exception { XSTest::Callback::first(sub { die "oops\n" }, [ 1 ]) }, qr/^oops/, "exception in the block";
+is XSTest::Callback::first(sub { 1 }, [ "after" ]), "after", "works after an exception";
+
+{
+    local $_ = "topic";
+    eval { XSTest::Callback::first(sub { die "oops\n" }, [ 1 ]) };
+    is $_, "topic", "\$_ restored after an exception";
+}
+
+{
+    my $err = exception { XSTest::Callback::reduce(sub { die { code => 1 } }, [ 1, 2 ]) };
+    is ref $err, "HASH", "exception object is kept";
+    is $err->{code}, 1, "exception object content";
+}
+
+is XSTest::Callback::first(\&XSTest::Callback::topic_len, [ "", "abc", "de" ]), "abc", "XSUB as the block";
+
+{
+    no warnings "once";
+    local *XSTest::Callback::thrower = sub { die { code => 2 } };
+    my $err = exception { XSTest::Callback::first(\&XSTest::Callback::call_thrower, [ 1 ]) };
+    is ref $err, "HASH", "exception object from an XSUB block is kept";
+    is $err->{code}, 2, "exception object content from an XSUB block";
+}
+
+{
+    my $depth = 0;
+    my $block;
+    $block = sub { $depth++ < 3 ? XSTest::Callback::first($block, [ $_ ]) : $_ };
+    is XSTest::Callback::first($block, [ "deep" ]), "deep", "recursive block";
+}
+
+done_testing;
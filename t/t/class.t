@@ -0,0 +1,80 @@
+use strict;
+use warnings;
+
+use Test::More;
+use Test::Fatal;
+use Test::LeakTrace;
+
+require_ok("XSTest");
+
+{
+    my $c = XSTest::Class->new(value => 1, name => "foo");
+    isa_ok $c, "XSTest::Class";
+    is $c->value, 1, "getter";
+    is $c->name, "foo", "optional field";
+    $c->inc;
+    is $c->value, 2, "method";
+    is $c->value(10), 10, "setter returns new value";
+    is $c->value, 10, "setter";
+    like exception { $c->name("bar") }, qr/read-only accessor/, "read-only field";
+    like exception { $c->value("bar") }, qr/invalid value for XSTest::Class::value/, "invalid value";
+}
+
+is(XSTest::Class->new(value => 1)->name, undef, "optional field omitted");
+
+{
+    my $c = XSTest::Class->new(value => 1);
+    my $callback;
+    no warnings "once";
+    local *XSTest::Class::callback = sub { $callback->() };
+
+    $callback = sub { is $c->value, 1, "getter of a read-write field while borrowed" };
+    is $c->with_callback, 1, "method returns";
+
+    $callback = sub { $c->value(2) };
+    like exception { $c->with_callback },
+        qr/invalid argument 'this' for XSTest::Class::value: .*already borrowed/,
+        "setter while borrowed";
+}
+
+like exception { XSTest::Class->new() }, qr/Missing field: value/, "missing field";
+like exception { XSTest::Class::inc(bless {}, "XSTest::Class") }, qr/invalid argument 'this'/, "not a Rust object";
+like exception { XSTest::Class::inc(bless {}, "Other") }, qr/not a XSTest::Class object/, "wrong class";
+like exception { XSTest::Class::new("Other", value => 1) }, qr/not a subclass/, "wrong class for new";
+
+{
+    package XSTest::Class::Sub;
+    our @ISA = ("XSTest::Class");
+}
+
+{
+    my $c = XSTest::Class::Sub->new(value => 5);
+    isa_ok $c, "XSTest::Class::Sub";
+    $c->inc;
+    is $c->value, 6, "subclass inherits methods";
+}
+
+{
+    package ReadsCounter;
+    sub TIESCALAR { bless { counter => $_[1] }, $_[0] }
+    sub FETCH { $_[0]{counter}->value + 1 }
+}
+
+{
+    my $c = XSTest::Class->new(value => 1);
+    tie my $next, "ReadsCounter", $c;
+    is $c->value($next), 2, "setter argument is converted before the object is borrowed";
+    is $c->value, 2, "value set";
+}
+
+no_leaks_ok {
+    my $c = XSTest::Class->new(value => 1, name => "foo");
+    $c->inc;
+    $c->value(2);
+    $c->name;
+};
+
+# Object that survives until global destruction.
+our $global = XSTest::Class->new(value => 1);
+
+done_testing;
@@ -0,0 +1,32 @@
+use strict;
+use warnings;
+use Test::More;
+use Test::Fatal;
+use Test::LeakTrace;
+
+require_ok("XSTest");
+
+is XSTest::test_st_iter(qw/a b c/), "a,b,c", "iterate over arguments";
+is XSTest::test_st_iter(), "", "no arguments";
+is XSTest::test_st_iter_len(1 .. 7), 7, "exact length";
+
+is_deeply [ XSTest::test_double_in_place(1, 2, 3) ], [ 2, 4, 6 ], "arguments replaced in place";
+is_deeply [ XSTest::test_double_in_place() ], [], "nothing to replace";
+{
+    my @args = (5, 6);
+    my @res = XSTest::test_double_in_place(@args);
+    is_deeply \@args, [ 5, 6 ], "caller variables are not modified";
+    is_deeply \@res, [ 10, 12 ], "new values returned";
+}
+
+is_deeply [ XSTest::test_return_first(2, "x", "y") ], [ 2, "x" ], "return leading arguments";
+is_deeply [ XSTest::test_return_first(0, "x") ], [], "return nothing";
+is_deeply [ XSTest::test_return_extra() ], [ qw/a b c d/ ], "stored values and returned value";
+is_deeply [ XSTest::test_store_past_args(1, 2) ], [ 1, 2, "c", "d" ], "store after the arguments";
+like exception { XSTest::test_return_first(5, "x") }, qr/can't return 5 values, the stack holds 2/, "return past the end";
+like exception { XSTest::test_store_gap() }, qr/stack index 2 leaves a gap after 0 values/, "store past the end";
+
+no_leaks_ok { my @x = XSTest::test_double_in_place(1 .. 10) };
+no_leaks_ok { my @x = XSTest::test_return_extra() };
+
+done_testing;
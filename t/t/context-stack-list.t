@@ -0,0 +1,22 @@
+use strict;
+use warnings;
+use Test::More;
+use Test::LeakTrace;
+
+require_ok("XSTest");
+
+is_deeply [ XSTest::test_push_vec(5) ], [ 0 .. 4 ], "Vec";
+is_deeply [ XSTest::test_push_vec(0) ], [], "empty Vec";
+is_deeply [ XSTest::test_push_vec(100_000) ], [ 0 .. 99_999 ], "large Vec";
+is scalar(my @list = XSTest::test_push_vec(3)), 3, "count in scalar context";
+
+is_deeply [ XSTest::test_push_slice() ], [ qw/foo bar baz/ ], "slice";
+is_deeply [ XSTest::test_push_list(3) ], [ qw/item0 item1 item2/ ], "List with exact size hint";
+is_deeply [ XSTest::test_push_filtered(10) ], [ 0, 3, 6, 9 ], "List without lower bound";
+is_deeply [ XSTest::test_push_filtered(10_000) ], [ grep { $_ % 3 == 0 } 0 .. 9_999 ], "List growing the stack";
+is_deeply [ XSTest::test_push_args(1 .. 50) ], [ 1 .. 50 ], "returning arguments";
+
+no_leaks_ok { my @x = XSTest::test_push_vec(10) };
+no_leaks_ok { my @x = XSTest::test_push_list(10) };
+
+done_testing;
@@ -0,0 +1,32 @@
+use strict;
+use warnings;
+
+use Test::More;
+use Test::LeakTrace;
+use B;
+
+require_ok("XSTest");
+
+my $obj = XSTest::Attach->new(42, "answer");
+is $obj->id, 42, "first value";
+is $obj->label, "answer", "second value";
+
+$obj->set_label("question");
+is $obj->label, "question", "mutable access by type";
+is $obj->id, 42, "other value not affected";
+
+is $obj->relabel("ultimate"), "question", "detach returns value";
+is $obj->label, "ultimate", "value attached again";
+
+ok !$obj->has_foreign, "no foreign data";
+
+$obj->relabel("again") for 1..100;
+is scalar(grep { $_->TYPE eq "~" } B::svref_2object($obj)->MAGIC), 2, "detached entries are reused";
+
+no_leaks_ok {
+    my $obj = XSTest::Attach->new(1, "one");
+    $obj->set_label("two");
+    $obj->relabel("three");
+};
+
+done_testing;
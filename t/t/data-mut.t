@@ -0,0 +1,37 @@
+use strict;
+use warnings;
+
+use Test::More;
+use Test::Fatal;
+use Test::LeakTrace;
+
+require_ok("XSTest");
+
+our $obj = XSTest::DataMut->new(0);
+$obj->inc;
+is $obj->get, 1, "mutable access";
+
+our $callback;
+sub XSTest::DataMut::callback { $callback->() }
+
+$callback = sub { $obj->get };
+like exception { $obj->inc_with_callback },
+    qr/invalid argument 'this' for XSTest::DataMut::get: XSTest::DataMut object is already borrowed mutably/,
+    "shared borrow while borrowed mutably";
+
+$callback = sub { $obj->inc };
+like exception { $obj->inc_with_callback },
+    qr/invalid argument 'this' for XSTest::DataMut::inc: XSTest::DataMut object is already borrowed mutably/,
+    "mutable borrow while borrowed mutably";
+
+$callback = sub { };
+is exception { $obj->inc_with_callback }, undef, "borrow is released after exception";
+is $obj->get, 4, "all increments happened";
+
+no_leaks_ok {
+    my $c = XSTest::DataMut->new(1);
+    $c->inc;
+    $c->get;
+};
+
+done_testing;
@@ -0,0 +1,38 @@
+use strict;
+use warnings;
+use Test::More;
+use Test::Fatal;
+
+require_ok("XSTest");
+
+XSTest::Magic::init();
+
+our $counter;
+*counter = \$XSTest::Magic::counter;
+
+is $counter, 1, "get runs on read";
+is $counter, 2, "get runs on every read";
+is XSTest::Magic::value(), 2, "rust state";
+
+$counter = 5;
+is XSTest::Magic::value(), 50, "set runs on assignment";
+is $counter, 51, "get sees new state";
+
+my $copy = $counter;
+is $copy, 52, "copy has the value";
+is $copy, 52, "copy is not magical";
+
+{
+    my $sv = 1;
+    XSTest::Magic::add_free_magic($sv);
+    is XSTest::Magic::freed(), 0, "not freed yet";
+}
+is XSTest::Magic::freed(), 1, "free runs when scalar is freed";
+
+{
+    my $sv;
+    XSTest::Magic::add_croaking_magic($sv);
+    like exception { my $x = $sv }, qr/^no value here/, "panic in callback becomes exception";
+}
+
+done_testing;
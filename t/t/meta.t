@@ -0,0 +1,21 @@
+use strict;
+use warnings;
+
+use Test::More;
+use Test::LeakTrace;
+
+require_ok("XSTest");
+
+my $sigs = XSTest::Param->__xs_signatures;
+is_deeply [ map { $_->{name} } @$sigs ], [ qw/add len strlen/ ], "subs are listed in order";
+
+my ($add, $len, $strlen) = @$sigs;
+is $add->{package}, "XSTest::Param", "package name";
+is_deeply $add->{params}, [ { name => "a", type => "IV" }, { name => "b", type => "IV" } ], "params";
+is $add->{doc}, "Add two numbers.", "one line doc";
+is $len->{doc}, "", "no doc";
+is $strlen->{doc}, "Count characters in a string.\n\nByte strings are treated as Latin-1.", "multi line doc";
+
+no_leaks_ok { XSTest::Param->__xs_signatures };
+
+done_testing;
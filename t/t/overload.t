@@ -0,0 +1,33 @@
+use strict;
+use warnings;
+use Test::More;
+use Test::LeakTrace;
+
+require_ok("XSTest");
+
+my $x = XSTest::Money->new(150);
+my $y = XSTest::Money->new(225);
+my $zero = XSTest::Money->new(0);
+
+is "$x", "1.50", "stringify";
+is 0+$y, 2.25, "numify";
+ok $x, "bool true";
+ok !$zero, "bool false";
+
+my $sum = $x + $y;
+isa_ok $sum, "XSTest::Money";
+is "$sum", "3.75", "add objects";
+is "" . ($x + 1), "2.50", "add number";
+is "" . (1 + $x), "2.50", "add swapped";
+
+is $x <=> $y, -1, "compare";
+is $y <=> $x, 1, "compare reversed";
+is 2 <=> $x, 1, "compare swapped";
+ok $x == XSTest::Money->new(150), "== via fallback";
+ok $x < $y, "< via fallback";
+ok $x eq "1.50", "eq via fallback";
+is_deeply [ map "$_", sort { $a <=> $b } $y, $zero, $x ], [ "0.00", "1.50", "2.25" ], "sort";
+
+no_leaks_ok { my $s = $x + $y; my $str = "$s"; my $c = $x <=> $y };
+
+done_testing;
@@ -0,0 +1,80 @@
+use strict;
+use warnings;
+use utf8;
+
+use Test::More;
+use Test::Fatal;
+
+require_ok("XSTest");
+
+package Stored {
+    sub TIESCALAR { my ($class) = @_; bless { stored => [] }, $class }
+    sub FETCH { die "should not be fetched" }
+    sub STORE { my ($self, $value) = @_; push @{$self->{stored}}, $value }
+}
+
+package main;
+
+{
+    my $n = XSTest::Alias::read_into(my $buf, 3);
+    is $n, 3, "return value";
+    is $buf, "xxx", "caller variable is filled";
+}
+
+{
+    my @bufs = ("a", "b");
+    XSTest::Alias::read_into($bufs[1], 2);
+    is_deeply \@bufs, [ "a", "xx" ], "array element is filled";
+}
+
+{
+    my $line = "text\n\n";
+    is XSTest::Alias::chomp_it($line), 2, "removed count";
+    is $line, "text", "string modified in place";
+}
+
+like exception { XSTest::Alias::read_into("literal", 1) },
+    qr/^invalid argument 'buf' for XSTest::Alias::read_into: modification of a read-only value attempted/, "literal rejected";
+like exception { XSTest::Alias::read_into(undef, 1) }, qr/read-only/, "undef literal rejected";
+
+{
+    my $obj = tie my $tied, "Stored";
+    XSTest::Alias::read_into($tied, 2);
+    is_deeply $obj->{stored}, [ "xx" ], "set-magic is processed, get-magic is not";
+}
+
+for my $case (
+    [ iv => -42 ],
+    [ uv => ~0 ],
+    [ nv => 0.5 ],
+    [ str => "ключ" ],
+    [ bytes => "\xff" ],
+    [ undef => undef ],
+    [ sv => "copied" ],
+) {
+    my ($kind, $expect) = @$case;
+    my $var = "old";
+    XSTest::Alias::set_kind($var, $kind);
+    is $var, $expect, "set $kind";
+}
+
+{
+    my $bytes = "";
+    XSTest::Alias::set_kind($bytes, "bytes");
+    ok !utf8::is_utf8($bytes), "bytes have no utf8 flag";
+    my $str = "";
+    XSTest::Alias::set_kind($str, "str");
+    ok utf8::is_utf8($str), "characters have utf8 flag";
+}
+
+{
+    my $obj = tie my $tied, "Stored";
+    XSTest::Alias::set_kind($tied, "iv");
+    is_deeply $obj->{stored}, [ -42 ], "setters process set-magic";
+}
+
+like exception { XSTest::Alias::set_kind(1, "iv") }, qr/^modification of a read-only value attempted/, "setter on a constant croaks";
+like exception { XSTest::Alias::set_kind(undef, "iv") }, qr/^modification of a read-only value attempted/, "setter on undef croaks";
+like exception { XSTest::Alias::set_kind(!!1, "str") }, qr/^modification of a read-only value attempted/, "setter on yes croaks";
+
+done_testing;
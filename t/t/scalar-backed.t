@@ -0,0 +1,38 @@
+use strict;
+use warnings;
+use utf8;
+use Test::More;
+use Test::Fatal;
+
+require_ok("XSTest");
+
+{
+    # Keep a reference to the SV itself, assignment would copy it.
+    my $data = \XSTest::Backed::bytes(5);
+    is $$data, "xxxxx", "buffer contents";
+    is length($$data), 5, "length";
+    ok !utf8::is_utf8($$data), "byte string";
+
+    like exception { $$data .= "y" }, qr/read-only/, "read-only";
+    like exception { substr($$data, 0, 1) = "y" }, qr/read-only/, "read-only substr";
+
+    my $copy = $$data;
+    $copy .= "y";
+    is $copy, "xxxxxy", "copy is writable";
+    is $$data, "xxxxx", "original unchanged";
+
+    ok $$data =~ /^x+$/, "regex match";
+    is XSTest::Backed::dropped(), 0, "owner alive";
+}
+is XSTest::Backed::dropped(), 1, "owner dropped with the scalar";
+
+is XSTest::Backed::bytes(3), "xxx", "returned value is copied";
+is XSTest::Backed::dropped(), 2, "owner dropped with the temporary";
+
+my $str = XSTest::Backed::table();
+is $str, "ключ", "static str";
+ok utf8::is_utf8($str), "character string";
+
+like exception { XSTest::Backed::unterminated() }, qr/must end with a NUL byte/, "buffer without a terminator";
+
+done_testing;
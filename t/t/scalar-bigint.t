@@ -0,0 +1,66 @@
+use strict;
+use warnings;
+
+use Test::More;
+use Test::Fatal;
+use Math::BigInt;
+use Math::BigFloat;
+
+require_ok("XSTest");
+
+my $i128_max = "170141183460469231731687303715884105727";
+my $i128_min = "-170141183460469231731687303715884105728";
+my $u128_max = "340282366920938463463374607431768211455";
+
+is XSTest::Primitive::to_i128(42), 42, "small integer";
+is XSTest::Primitive::to_i128(-42), -42, "small negative integer";
+is XSTest::Primitive::to_i128(~0), ~0, "UV max";
+is XSTest::Primitive::to_i128(2**60 * 8), "9223372036854775808", "integral float";
+is XSTest::Primitive::to_i128($i128_max), $i128_max, "i128 max from a string";
+is XSTest::Primitive::to_i128($i128_min), $i128_min, "i128 min from a string";
+is XSTest::Primitive::to_u128($u128_max), $u128_max, "u128 max from a string";
+is XSTest::Primitive::to_i128(" +17 "), 17, "whitespace and sign";
+
+ok !ref XSTest::Primitive::to_i128($i128_max), "large value is returned as a string";
+is ref XSTest::Primitive::to_bigint($i128_max), "Math::BigInt", "large value is returned as Math::BigInt";
+ok !ref XSTest::Primitive::to_bigint(5), "small value is returned as a number";
+
+is XSTest::Primitive::to_i128(Math::BigInt->new($i128_max)), $i128_max, "Math::BigInt argument";
+is XSTest::Primitive::to_u128(Math::BigFloat->new($u128_max)), $u128_max, "integral Math::BigFloat argument";
+is XSTest::Primitive::add_i128(Math::BigInt->new("100000000000000000000"), "1"), "100000000000000000001", "arithmetic";
+
+like exception { XSTest::Primitive::to_i128("170141183460469231731687303715884105728") },
+    qr/value 170141183460469231731687303715884105728 out of range for i128/, "i128 overflow";
+like exception { XSTest::Primitive::to_u128(-1) }, qr/value -1 out of range for u128/, "negative u128";
+like exception { XSTest::Primitive::to_u128("-1") }, qr/value -1 out of range for u128/, "negative u128 string";
+like exception { XSTest::Primitive::to_i128(Math::BigFloat->new("1.5")) }, qr/'1.5' is not an integer/, "fractional Math::BigFloat";
+like exception { XSTest::Primitive::to_i128(1.5) }, qr/'1.5' is not an integer/, "fractional number";
+like exception { XSTest::Primitive::to_i128(1e40) }, qr/out of range for i128/, "integral float overflow";
+like exception { XSTest::Primitive::to_u128(-1e20) }, qr/out of range for u128/, "negative integral float";
+like exception { XSTest::Primitive::to_i128("12abc") }, qr/'12abc' is not a number/, "garbage";
+like exception { XSTest::Primitive::to_i128(undef) }, qr/undef is not a number/, "undef";
+like exception { XSTest::Primitive::to_i128(Math::BigInt->bnan) }, qr/'NaN' is not a number/, "Math::BigInt NaN";
+
+my @list = XSTest::Primitive::bigint_list($i128_max);
+is_deeply [ map "$_", @list ], [ 1, "two", $i128_max ], "Math::BigInt after other values";
+is ref $list[2], "Math::BigInt", "last value of a list is Math::BigInt";
+
+my @vec = XSTest::Primitive::bigint_vec($i128_max);
+is_deeply [ map "$_", @vec ], [ $i128_max, -1, $i128_max ], "vector of BigInt";
+is_deeply [ map ref, @vec ], [ "Math::BigInt", "", "Math::BigInt" ], "only large values are objects";
+
+{
+    local $@ = "keep";
+    XSTest::Primitive::to_bigint($i128_max);
+    is $@, "keep", "\$@ is preserved";
+}
+
+{
+    local %INC = %INC;
+    delete $INC{"Math/BigInt.pm"};
+    local @INC = (sub { die "Math::BigInt is not available\n" });
+    like exception { XSTest::Primitive::to_bigint($i128_max) }, qr/can't create Math::BigInt: Math::BigInt is not available/,
+        "Math::BigInt fails to load";
+}
+
+done_testing;
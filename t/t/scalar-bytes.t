@@ -0,0 +1,31 @@
+use strict;
+use warnings;
+use utf8;
+use Test::More;
+use Test::Fatal;
+use Encode ();
+
+require_ok("XSTest");
+
+my $bin = "\x00\xff\x80abc";
+is_deeply [ XSTest::test_bytes($bin) ], [ 6, $bin ], "binary round trip";
+ok !utf8::is_utf8((XSTest::test_bytes($bin))[1]), "result has no utf8 flag";
+
+my $upgraded = "caf\xe9";
+utf8::upgrade($upgraded);
+is_deeply [ XSTest::test_bytes($upgraded) ], [ 4, "caf\xe9" ], "upgraded string is downgraded";
+
+like exception { XSTest::test_bytes("あ") }, qr/wide character at offset 0/, "wide characters rejected";
+like exception { XSTest::test_vec_u8("abcあ") }, qr/wide character at offset 3/, "wide characters rejected for Vec<u8>";
+
+my $malformed = "ab\xff";
+Encode::_utf8_on($malformed);
+like exception { XSTest::test_bytes($malformed) }, qr/malformed UTF-8/, "malformed UTF-8 rejected";
+
+is XSTest::test_vec_u8($bin), $bin, "Vec<u8> round trip";
+
+my $slice = XSTest::test_byte_slice();
+is $slice, "\x00\xffbinary", "byte slice";
+ok !utf8::is_utf8($slice), "byte slice has no utf8 flag";
+
+done_testing;
@@ -0,0 +1,58 @@
+use strict;
+use warnings;
+use Test::More;
+use Scalar::Util qw/refaddr/;
+
+require_ok("XSTest");
+
+package Counted {
+    sub TIESCALAR { my ($class, $value) = @_; bless { value => $value, fetched => 0 }, $class }
+    sub FETCH { my $self = shift; $self->{fetched}++; $self->{value} }
+    sub STORE { my ($self, $value) = @_; $self->{value} = $value }
+}
+
+package Overloaded {
+    use overload
+        '""' => sub { "str:" . $_[0]{value} },
+        '0+' => sub { $_[0]{value} },
+        'bool' => sub { $_[0]{value} > 10 },
+        fallback => 1;
+    sub new { bless { value => $_[1] }, $_[0] }
+}
+
+package main;
+
+for my $case (
+    [ "iv", \&XSTest::test_conv_iv, 42, 42 ],
+    [ "opt_iv", \&XSTest::test_conv_opt_iv, 42, 42 ],
+    [ "opt_iv undef", \&XSTest::test_conv_opt_iv, undef, undef ],
+    [ "nv", \&XSTest::test_conv_nv, 1.5, 1.5 ],
+    [ "str", \&XSTest::test_conv_str, "foo", "foo" ],
+    [ "bool", \&XSTest::test_conv_bool, 1, !!1 ],
+) {
+    my ($name, $sub, $value, $expect) = @$case;
+    my $obj = tie my $tied, "Counted", $value;
+    is $sub->($tied), $expect, "$name: tied value";
+    is $obj->{fetched}, 1, "$name: fetched once";
+}
+
+my $obj = Overloaded->new(42);
+is XSTest::test_conv_iv($obj), 42, "iv via 0+";
+is XSTest::test_conv_nv(Overloaded->new(2.5)), 2.5, "nv via 0+";
+is XSTest::test_conv_str($obj), "str:42", "string via \"\"";
+ok XSTest::test_conv_bool($obj), "bool via bool";
+ok !XSTest::test_conv_bool(Overloaded->new(5)), "bool via bool, false";
+
+is XSTest::test_conv_raw_iv($obj), refaddr($obj), "raw iv is the address";
+like XSTest::test_conv_raw_str($obj), qr/^Overloaded=HASH\(0x[0-9a-f]+\)$/, "raw string";
+like XSTest::test_conv_raw_str([]), qr/^ARRAY\(0x[0-9a-f]+\)$/, "raw string of plain ref";
+is XSTest::test_conv_raw_iv(7), 7, "raw iv of a number";
+is XSTest::test_conv_raw_str("plain"), "plain", "raw string of a string";
+
+{
+    my $obj = tie my $tied, "Counted", "foo";
+    is XSTest::test_conv_raw_str($tied), "", "raw skips get-magic";
+    is $obj->{fetched}, 0, "not fetched";
+}
+
+done_testing;
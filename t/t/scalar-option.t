@@ -0,0 +1,41 @@
+use strict;
+use warnings;
+
+use Test::More;
+use Test::Fatal;
+
+require_ok("XSTest");
+
+package Counted {
+    sub TIESCALAR { my ($class, $value) = @_; bless { value => $value, fetched => 0 }, $class }
+    sub FETCH { my $self = shift; $self->{fetched}++; $self->{value} }
+}
+
+package main;
+
+is XSTest::test_conv_opt_str("foo"), "foo", "Option<String>: defined";
+is XSTest::test_conv_opt_str(undef), undef, "Option<String>: undef";
+is XSTest::test_conv_opt_str(""), "", "Option<String>: empty string is defined";
+
+is XSTest::test_conv_opt_u8(0), 0, "Option<u8>: zero is defined";
+is XSTest::test_conv_opt_u8(undef), undef, "Option<u8>: undef";
+like exception { XSTest::test_conv_opt_u8(256) }, qr/value 256 out of range for u8/, "Option<u8>: inner error";
+
+is_deeply [ XSTest::test_conv_opt_kinds([ 1, 2 ], {}, 0) ], [ 2, 1, 1 ], "Option<AV>, Option<HV>, Option<SV>: defined";
+is_deeply [ XSTest::test_conv_opt_kinds(undef, undef, undef) ], [ undef, "", "" ], "Option<AV>, Option<HV>, Option<SV>: undef";
+like exception { XSTest::test_conv_opt_kinds({}, undef, undef) }, qr/invalid argument 'av'/, "Option<AV>: wrong type";
+
+{
+    my $c = XSTest::Data->new(5);
+    is XSTest::Data::get_or($c, 0), 5, "Option<DataRef>: object";
+    is XSTest::Data::get_or(undef, 7), 7, "Option<DataRef>: undef";
+    like exception { XSTest::Data::get_or("str", 0) }, qr/invalid argument 'this'/, "Option<DataRef>: not an object";
+}
+
+for my $value ("foo", undef) {
+    my $obj = tie my $tied, "Counted", $value;
+    is XSTest::test_conv_opt_str($tied), $value, "tied value";
+    is $obj->{fetched}, 1, "fetched once";
+}
+
+done_testing;
@@ -0,0 +1,45 @@
+use strict;
+use warnings;
+use utf8;
+use Test::More;
+use Test::Fatal;
+
+require_ok("XSTest");
+
+my $text = "hello";
+my $error;
+sub XSTest::Pinned::callback {
+    $error = exception { $text .= " world" };
+}
+
+is_deeply [ XSTest::Pinned::count_with_callback($text) ], [ 5, "hello" ], "pinned value is unchanged";
+is $error, undef, "original stays writable while pinned";
+is $text, "hello world", "modification of the original";
+
+{
+    my $upgrade = "caf\xe9" x 1000;
+    local *XSTest::Pinned::callback = sub { utf8::upgrade($upgrade); $upgrade .= "!" };
+    is_deeply [ XSTest::Pinned::count_with_callback($upgrade) ], [ 4000, "caf\xe9" x 1000 ], "buffer survives utf8::upgrade";
+}
+
+$text = 42;
+is_deeply [ XSTest::Pinned::count_with_callback($text) ], [ 2, "42" ], "number is stringified";
+
+{
+    tie my $tied, "Tied";
+    is_deeply [ XSTest::Pinned::count_with_callback($tied) ], [ 3, "あいう" ], "magical value is copied";
+}
+
+my $ref = [];
+like +(XSTest::Pinned::count_with_callback($ref))[1], qr/^ARRAY/, "reference is stringified";
+
+my $str = "abc";
+is_deeply [ XSTest::Pinned::nested_pins($str) ], [ "abc", !!0, !!0 ], "nested pins";
+$str = "def";
+is $str, "def", "writable after nested pins";
+
+done_testing;
+
+package Tied;
+sub TIESCALAR { bless {}, shift }
+sub FETCH { "あいう" }
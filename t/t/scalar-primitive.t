@@ -0,0 +1,66 @@
+use strict;
+use warnings;
+use utf8;
+
+use Test::More;
+use Test::Fatal;
+
+require_ok("XSTest");
+
+my %range = (
+    i8 => [ -128, 127 ],
+    i16 => [ -32768, 32767 ],
+    i32 => [ -2147483648, 2147483647 ],
+    u8 => [ 0, 255 ],
+    u16 => [ 0, 65535 ],
+    u32 => [ 0, 4294967295 ],
+);
+
+for my $ty (sort keys %range) {
+    my $sub = XSTest::Primitive->can("to_$ty");
+    my ($min, $max) = @{$range{$ty}};
+    is $sub->($min), $min, "$ty: min";
+    is $sub->($max), $max, "$ty: max";
+    is $sub->("$max"), $max, "$ty: string";
+    like exception { $sub->($min - 1) }, qr/value @{[ $min - 1 ]} out of range for $ty/, "$ty: below min";
+    like exception { $sub->($max + 1) }, qr/value @{[ $max + 1 ]} out of range for $ty/, "$ty: above max";
+}
+
+is XSTest::Primitive::to_u8(300.5 - 100), 200, "fraction is truncated";
+like exception { XSTest::Primitive::to_u8(300) }, qr/^invalid argument 'v' for XSTest::Primitive::to_u8: value 300 out of range for u8/,
+    "error message";
+is XSTest::Primitive::to_isize(-5), -5, "isize";
+is XSTest::Primitive::to_usize(~0), ~0, "usize max";
+like exception { XSTest::Primitive::to_usize(-1) }, qr/value -1 out of range for usize/, "usize negative";
+like exception { XSTest::Primitive::to_isize(~0) }, qr/value ${\ ~0} out of range for isize/, "isize from large unsigned";
+
+is XSTest::Primitive::to_f32(1.5), 1.5, "f32";
+like exception { XSTest::Primitive::to_f32(1e39) }, qr/out of range for f32/, "f32 overflow";
+is XSTest::Primitive::to_f32(9**9**9), 9**9**9, "f32 infinity";
+
+is XSTest::Primitive::to_char("é"), "é", "char";
+is XSTest::Primitive::to_char(7), "7", "char from number";
+like exception { XSTest::Primitive::to_char("ab") }, qr/expected a single character, got 2/, "char from long string";
+like exception { XSTest::Primitive::to_char("") }, qr/expected a single character, got 0/, "char from empty string";
+
+is XSTest::Primitive::to_boxed_str("ключ"), "ключ", "Box<str>";
+is XSTest::Primitive::to_cow("ключ"), "ключ", "Cow<str>";
+is XSTest::Primitive::to_arc_str("ключ"), "ключ", "Arc<str>";
+is XSTest::Primitive::to_rc(42), 42, "Rc<u8>";
+like exception { XSTest::Primitive::to_rc(256) }, qr/out of range for u8/, "Rc<u8> range";
+is_deeply [ XSTest::Primitive::shared_twice("x") ], [ "x", "x" ], "shared value is cloned";
+
+{
+    my $s = XSTest::Primitive::to_os_string("caf\xe9");
+    is $s, "caf\xe9", "OsString";
+    ok !utf8::is_utf8($s), "OsString is bytes";
+    is XSTest::Primitive::to_path("/tmp"), "/tmp/file", "PathBuf";
+    like exception { XSTest::Primitive::to_os_string("ключ") }, qr/wide character/, "OsString from wide string";
+}
+
+is XSTest::Primitive::to_duration(1.5), 1.5, "Duration";
+is XSTest::Primitive::duration_millis(2.25), 2250, "Duration fraction";
+like exception { XSTest::Primitive::to_duration(-1) }, qr/value -1 out of range for Duration/, "negative Duration";
+like exception { XSTest::Primitive::to_duration(9**9**9) }, qr/out of range for Duration/, "infinite Duration";
+
+done_testing;
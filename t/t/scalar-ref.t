@@ -0,0 +1,38 @@
+use strict;
+use warnings;
+use Test::More;
+use Test::LeakTrace;
+use Scalar::Util qw/refaddr/;
+
+require_ok("XSTest");
+
+my $arr = [];
+is_deeply [ XSTest::test_ref_info($arr) ], [ refaddr($arr), "ARRAY", undef, "" ], "array ref";
+my $s = 1;
+is_deeply [ XSTest::test_ref_info(\$s) ], [ refaddr(\$s), "SCALAR", undef, "" ], "scalar ref";
+
+my $obj = bless {}, "Foo::Bar";
+is_deeply [ XSTest::test_ref_info($obj) ], [ refaddr($obj), "HASH", "Foo::Bar", "" ], "blessed ref";
+
+my $code = bless sub {}, "Foo";
+is_deeply [ (XSTest::test_ref_info($code))[1, 2] ], [ "CODE", "Foo" ], "blessed code ref";
+
+is_deeply [ XSTest::test_ref_info(42) ], [ undef, undef, undef, "" ], "not a ref";
+is_deeply [ XSTest::test_ref_info("Foo::Bar") ], [ undef, undef, undef, "" ], "package name";
+
+{
+    my $target = {};
+    my $ref = $target;
+    ok XSTest::test_weaken($ref), "weakened";
+    ok !(XSTest::test_ref_info($target))[3], "original ref is strong";
+    ok +(XSTest::test_ref_info($ref))[3], "weak ref reported";
+    undef $target;
+    ok !defined $ref, "weak ref cleared";
+}
+
+ok !XSTest::test_weaken(42), "weaken on non-reference";
+
+no_leaks_ok { XSTest::test_ref_info($obj) };
+no_leaks_ok { my $x = {}; my $y = $x; XSTest::test_weaken($y) };
+
+done_testing;
@@ -0,0 +1,64 @@
+use strict;
+use warnings;
+
+use Test::More;
+use Test::Fatal;
+
+require_ok("XSTest");
+
+package Counted {
+    sub TIESCALAR { my ($class, $value) = @_; bless { value => $value, fetched => 0 }, $class }
+    sub FETCH { my $self = shift; $self->{fetched}++; $self->{value} }
+}
+
+package Overloaded {
+    use overload '0+' => sub { 42 }, fallback => 1;
+}
+
+package main;
+
+is XSTest::test_conv_strict_iv(42), 42, "integer";
+is XSTest::test_conv_strict_iv("-17"), -17, "numeric string";
+is XSTest::test_conv_strict_iv(" 12 "), 12, "whitespace around a number";
+is XSTest::test_conv_strict_nv("1.5e3"), 1500, "exponent";
+is XSTest::test_conv_strict_nv("0.25"), 0.25, "fraction";
+is XSTest::test_conv_strict_iv(!!1), 1, "boolean true";
+
+for my $case (
+    [ "undef", undef, qr/undef is not a number/ ],
+    [ "empty string", "", qr/'' is not a number/ ],
+    [ "word", "abc", qr/'abc' is not a number/ ],
+    [ "trailing garbage", "12abc", qr/'12abc' is not a number/ ],
+    [ "reference", [], qr/reference is not a number/ ],
+    [ "overloaded object", bless({}, "Overloaded"), qr/reference is not a number/ ],
+) {
+    my ($name, $value, $error) = @$case;
+    like exception { XSTest::test_conv_strict_iv($value) }, qr/^invalid argument 'iv' for XSTest::test_conv_strict_iv: $error/,
+        "iv: $name";
+    like exception { XSTest::test_conv_strict_nv($value) }, $error, "nv: $name";
+}
+
+is XSTest::test_conv_strict_iv("1e3"), 1000, "integer with an exponent";
+like exception { XSTest::test_conv_strict_iv("2.5") }, qr/'2.5' is not an integer/, "fraction rejected for iv";
+like exception { XSTest::test_conv_strict_iv(2.5) }, qr/'2.5' is not an integer/, "fractional number rejected for iv";
+like exception { XSTest::test_conv_strict_iv("1e30") }, qr/value 1e30 out of range for IV/, "iv range";
+like exception { XSTest::test_conv_strict_iv("-1e30") }, qr/value -1e30 out of range for IV/, "negative iv range";
+
+is XSTest::test_conv_strict_u8("200"), 200, "narrow integer";
+like exception { XSTest::test_conv_strict_u8("2.5") }, qr/'2.5' is not an integer/, "fraction rejected for narrow integer";
+like exception { XSTest::test_conv_strict_u8("300") }, qr/value 300 out of range for u8/, "narrow integer range";
+like exception { XSTest::test_conv_strict_u8("x") }, qr/'x' is not a number/, "narrow integer from a word";
+
+{
+    my $obj = tie my $tied, "Counted", "7";
+    is XSTest::test_conv_strict_iv($tied), 7, "tied value";
+    is $obj->{fetched}, 1, "fetched once";
+}
+
+{
+    my $obj = tie my $tied, "Counted", "seven";
+    like exception { XSTest::test_conv_strict_iv($tied) }, qr/'seven' is not a number/, "tied non-number";
+    is $obj->{fetched}, 1, "fetched once";
+}
+
+done_testing;
@@ -25,4 +25,40 @@ is_deeply [ XSTest::test_unicode(0) ], [ "0", 0, UTF_OK ], "number is ok";
 
 is_deeply [ XSTest::test_unicode(NONUTF) ], [ undef, 0+utf8::is_utf8(NONUTF), NONUTF_OFFSET ], "binary not ok";
 
+use constant LATIN1 => "caf\xe9";
+
+is_deeply [ XSTest::test_str(LATIN1) ], [ 4, LATIN1 ], "latin-1 byte string";
+ok !utf8::is_utf8(LATIN1), "latin-1 constant has no utf8 flag";
+is_deeply [ XSTest::test_str(KANA) ], [ 5, KANA ], "character string";
+is_deeply [ XSTest::test_str(NONUTF) ], [ 12, NONUTF ], "binary is latin-1";
+ok utf8::is_utf8((XSTest::test_str(ASCII))[1]), "ascii result has utf8 flag";
+ok utf8::is_utf8((XSTest::test_str(LATIN1))[1]), "non-ascii result has utf8 flag";
+
+is_deeply [ XSTest::test_as_str(ASCII) ], [ ASCII, 1 ], "as_str borrows ascii";
+is_deeply [ XSTest::test_as_str(KANA) ], [ KANA, 1 ], "as_str borrows utf8";
+is_deeply [ XSTest::test_as_str(LATIN1) ], [ LATIN1, 0 ], "as_str decodes latin-1";
+is_deeply [ XSTest::test_cow_str(ASCII) ], [ ASCII, 1 ], "Cow<str> borrows ascii";
+is_deeply [ XSTest::test_cow_str(KANA) ], [ KANA, 1 ], "Cow<str> borrows utf8";
+is_deeply [ XSTest::test_cow_str(LATIN1) ], [ LATIN1, 0 ], "Cow<str> decodes latin-1";
+
+{
+    my $s = LATIN1;
+    XSTest::test_utf8_upgrade($s);
+    ok utf8::is_utf8($s), "upgrade sets flag";
+    is $s, LATIN1, "upgrade keeps characters";
+    ok XSTest::test_utf8_downgrade($s), "downgrade succeeds";
+    ok !utf8::is_utf8($s), "downgrade clears flag";
+    is $s, LATIN1, "downgrade keeps characters";
+
+    my $k = KANA;
+    ok !XSTest::test_utf8_downgrade($k), "downgrade of wide characters fails";
+    is $k, KANA, "failed downgrade keeps string";
+
+    XSTest::test_utf8_encode($k);
+    ok !utf8::is_utf8($k), "encode clears flag";
+    is length($k), 15, "encode produces bytes";
+    ok XSTest::test_utf8_decode($k), "decode succeeds";
+    is $k, KANA, "decode restores characters";
+}
+
 done_testing;
@@ -0,0 +1,28 @@
+use strict;
+use warnings;
+use utf8;
+use Test::More;
+use Test::LeakTrace;
+
+require_ok("XSTest");
+
+is XSTest::Writer::repeat("a", 3), "a0a1a2", "formatted";
+ok !utf8::is_utf8(XSTest::Writer::repeat("a", 3)), "ascii has no utf8 flag";
+
+my $wide = XSTest::Writer::repeat("ж", 2);
+is $wide, "ж0ж1", "characters";
+ok utf8::is_utf8($wide), "utf8 flag set";
+
+my $long = XSTest::Writer::repeat("x" x 100, 1000);
+is length($long), 100 * 1000 + 10 + 90 * 2 + 900 * 3, "buffer grows";
+
+my $bin = XSTest::Writer::binary(3);
+is $bin, "\xff\x00\x01\x00\x00", "binary";
+ok !utf8::is_utf8($bin), "binary has no utf8 flag";
+
+ok XSTest::Writer::invalid_utf8(), "invalid utf8 rejected";
+
+no_leaks_ok { XSTest::Writer::repeat("abc", 100) };
+no_leaks_ok { XSTest::Writer::invalid_utf8() };
+
+done_testing;
@@ -0,0 +1,97 @@
+use strict;
+use warnings;
+
+use Test::More;
+use Test::LeakTrace;
+
+require_ok("XSTest");
+
+our $destroyed = 0;
+our @seen;
+
+sub XSTest::Scope::Tracked::DESTROY { $destroyed++ }
+sub XSTest::Scope::checkpoint { push @seen, $destroyed }
+
+sub run {
+    my ($sub, $n) = @_;
+    local $destroyed = 0;
+    local @seen;
+    $sub->($n);
+    return ([ @seen ], $destroyed);
+}
+
+{
+    my ($seen, $total) = run(\&XSTest::Scope::without_scope, 3);
+    is_deeply $seen, [ 0, 0, 0 ], "temporaries accumulate without a scope";
+    is $total, 3, "freed after return";
+}
+
+{
+    my ($seen, $total) = run(\&XSTest::Scope::with_scope, 3);
+    is_deeply $seen, [ 0, 1, 2 ], "temporaries freed on each iteration";
+    is $total, 3, "all freed";
+}
+
+{
+    my ($seen, $total) = run(\&XSTest::Scope::scope_end, 4);
+    is_deeply $seen, [ 4 ], "temporaries freed when the scope is dropped";
+}
+
+is XSTest::Scope::owned_survives(), "kept", "owned values survive the scope";
+
+$XSTest::Scope::var = "outer";
+@XSTest::Scope::list = ("outer");
+%XSTest::Scope::map = (key => "outer");
+our @observed;
+sub XSTest::Scope::observe { push @observed, $XSTest::Scope::var }
+sub XSTest::Scope::observe_topic { push @observed, $_ }
+sub XSTest::Scope::modify_topic { $_ .= "!" }
+sub XSTest::Scope::observe_containers { push @observed, [ @XSTest::Scope::list ], { %XSTest::Scope::map } }
+
+{
+    local @observed;
+    XSTest::Scope::local_var();
+    is_deeply \@observed, [ "inner", "outer" ], "local scalar";
+    is $XSTest::Scope::var, "outer", "restored";
+}
+
+{
+    local @observed;
+    local $_ = "topic";
+    XSTest::Scope::local_topic([ 1, 2, 3 ]);
+    is_deeply \@observed, [ 1, 2, 3 ], "local \$_";
+    is $_, "topic", "\$_ restored";
+}
+
+{
+    local $_ = "topic";
+    my @items = qw/a b/;
+    XSTest::Scope::alias_topic(\@items);
+    is_deeply \@items, [ "a!", "b!" ], "\$_ aliased to elements";
+    is $_, "topic", "\$_ restored after aliasing";
+    no_leaks_ok { XSTest::Scope::alias_topic([ 1, 2 ]) } "aliasing does not leak";
+}
+
+{
+    local @observed;
+    XSTest::Scope::local_containers();
+    is_deeply \@observed, [ [ "local" ], { key => "local" } ], "local array and hash";
+    is_deeply \@XSTest::Scope::list, [ "outer" ], "array restored";
+    is_deeply \%XSTest::Scope::map, { key => "outer" }, "hash restored";
+}
+
+{
+    local @observed;
+    XSTest::Scope::local_helem(\%XSTest::Scope::map);
+    is_deeply $observed[1], { key => "local", new => "local" }, "local hash elements";
+    is_deeply \%XSTest::Scope::map, { key => "outer" }, "hash elements restored";
+}
+
+{
+    local @observed;
+    XSTest::Scope::local_value($XSTest::Scope::var);
+    is_deeply \@observed, [ "temporary" ], "saved value changed";
+    is $XSTest::Scope::var, "outer", "saved value restored";
+}
+
+done_testing;
@@ -0,0 +1,46 @@
+use strict;
+use warnings;
+
+use Config;
+use Scalar::Util qw/blessed/;
+use Test::More;
+
+BEGIN {
+    plan skip_all => "perl is built without ithreads" unless $Config{useithreads};
+}
+
+use threads;
+
+require_ok("XSTest");
+
+my $data = XSTest::DataMut->new(1);
+my $counter = XSTest::Class->new(value => 1);
+my $point = XSTest::Class::Point->new(x => 1, y => 2);
+
+my @res = threads->create(sub {
+    my $data_err = eval { $data->get; 1 } ? "" : $@;
+    return (
+        $data_err,
+        !defined $$counter && !blessed($counter) ? 1 : 0,
+        $point->x,
+        $point->y,
+    );
+})->join;
+
+like $res[0], qr/XSTest::DataMut object is not available in this thread/, "non-cloneable data is invalidated";
+is $res[1], 1, "CLONE_SKIP is generated for non-cloneable classes";
+is_deeply [ @res[2, 3] ], [ 1, 2 ], "cloneable class is cloned";
+
+{
+    my $magical = 7;
+    XSTest::Magic::add_free_magic($magical);
+    my ($value) = threads->create(sub { $magical + 0 })->join;
+    is $value, 7, "magical scalar is cloned without callbacks";
+    is XSTest::Magic::freed(), 0, "callbacks are not freed by the thread";
+}
+is XSTest::Magic::freed(), 1, "callbacks are freed once by the parent";
+
+is $data->get, 1, "parent data still usable";
+is $counter->value, 1, "parent object still usable";
+
+done_testing;
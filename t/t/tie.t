@@ -0,0 +1,94 @@
+use strict;
+use warnings;
+use Test::More;
+use Test::Fatal;
+use Test::LeakTrace;
+
+require_ok("XSTest");
+
+{
+    my $obj = tie my %h, "XSTest::Tie::Hash", b => 2, a => 1;
+    isa_ok $obj, "XSTest::Tie::Hash";
+
+    is $h{a}, 1, "fetch initial";
+    $h{c} = 3;
+    is $h{c}, 3, "store";
+    ok exists $h{b}, "exists";
+    ok !exists $h{x}, "not exists";
+    is_deeply [ keys %h ], [ qw/a b c/ ], "keys in order";
+    is_deeply { %h }, { a => 1, b => 2, c => 3 }, "copy";
+    is delete $h{b}, 2, "delete";
+    ok !exists $h{b}, "deleted";
+    %h = ();
+    is_deeply [ keys %h ], [], "clear";
+}
+
+like exception { tie my %h, "XSTest::Tie::Hash", "a" },
+    qr/^XSTest::Tie::Hash::TIEHASH: odd number of arguments/, "tie error";
+
+{
+    tie my @a, "XSTest::Tie::Array";
+    push @a, 1, 2, 3;
+    is_deeply [ @a ], [ 1, 2, 3 ], "push";
+    is scalar(@a), 3, "size";
+    unshift @a, -1, 0;
+    is_deeply [ @a ], [ -1, 0, 1, 2, 3 ], "unshift";
+    is shift @a, -1, "shift";
+    is pop @a, 3, "pop";
+    is_deeply [ @a ], [ 0, 1, 2 ], "after shift and pop";
+    $a[5] = 5;
+    is_deeply [ @a ], [ 0, 1, 2, 0, 0, 5 ], "store extends";
+    $#a = 1;
+    is_deeply [ @a ], [ 0, 1 ], "store size";
+    like exception { delete $a[0] }, qr/does not support delete/, "delete";
+    @a = ();
+    is scalar(@a), 0, "clear";
+}
+
+{
+    tie my @a, "XSTest::Tie::Array";
+    push @a, 1 .. 5;
+    is_deeply [ splice @a, 1, 2, 7, 8, 9 ], [ 2, 3 ], "splice returns removed elements";
+    is_deeply [ @a ], [ 1, 7, 8, 9, 4, 5 ], "splice grows";
+    is scalar(splice @a, -3, 2), 4, "splice in scalar context";
+    is_deeply [ @a ], [ 1, 7, 8, 5 ], "splice shrinks";
+    splice @a, 1;
+    is_deeply [ @a ], [ 1 ], "splice to the end";
+}
+
+{
+    tie my @a, "XSTest::Tie::Sparse";
+    @a = (1, 2, 3);
+    delete $a[1];
+    is shift @a, 1, "shift before a hole";
+    ok !exists $a[0], "hole moved by shift";
+    is_deeply [ @a ], [ undef, 3 ], "elements after shift";
+    unshift @a, 0;
+    ok !exists $a[1], "hole moved by unshift";
+    is_deeply [ @a ], [ 0, undef, 3 ], "elements after unshift";
+    splice @a, 0, 1;
+    ok !exists $a[0], "hole moved by splice";
+}
+
+{
+    tie my $s, "XSTest::Tie::Scalar", 41;
+    is $s, 41, "fetch";
+    $s++;
+    is $s, 42, "store";
+}
+
+no_leaks_ok {
+    tie my %h, "XSTest::Tie::Hash", a => 1;
+    $h{b} = $h{a};
+    my @k = keys %h;
+    untie %h;
+};
+
+no_leaks_ok {
+    tie my @a, "XSTest::Tie::Array";
+    push @a, 1, 2;
+    my @b = @a;
+    untie @a;
+};
+
+done_testing;